@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use crate::state::{GraduationMode, MigrationVenue, MigrationFee, LpMode, LeftoverMode, RentDestination};
 
 // Deployer — only this wallet can call initialize
 pub const DEPLOYER_PUBKEY: Pubkey = Pubkey::new_from_array(DEPLOYER_BYTES);
@@ -16,6 +17,13 @@ pub const GLOBAL_SEED: &[u8] = b"global";
 pub const BONDING_CURVE_SEED: &[u8] = b"bonding-curve";
 pub const FEE_VAULT_SEED: &[u8] = b"fee-vault";
 pub const REFERRAL_SEED: &[u8] = b"referral";
+pub const WALLET_PURCHASE_SEED: &[u8] = b"wallet-purchase";
+pub const VESTING_SEED: &[u8] = b"vesting";
+pub const LP_LOCK_SEED: &[u8] = b"lp-lock";
+pub const GRADUATION_SEED: &[u8] = b"graduation";
+pub const SWAP_WSOL_SEED: &[u8] = b"swap-wsol";
+pub const MIGRATION_TOKEN_SEED: &[u8] = b"migration-token";
+pub const MIGRATION_WSOL_SEED: &[u8] = b"migration-wsol";
 
 //  Unit Helpers
 pub const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
@@ -31,8 +39,41 @@ pub const DEFAULT_TOKEN_SUPPLY: u64 = 1_000_000_000 * TOKEN_DECIMALS_FACTOR;
 // Fee Config (basis points, 10_000 = 100%) 
 pub const DEFAULT_TRADE_FEE_BPS: u16 = 100;        // 1%
 pub const DEFAULT_CREATOR_SHARE_BPS: u16 = 6_500;   // 65% de la fee
-pub const DEFAULT_REFERRAL_SHARE_BPS: u16 = 1_000;   // 10% de la fee
+pub const DEFAULT_REFERRAL_SHARE_BPS: u16 = 1_000;   // 10% de la fee, pris sur la part creator
+pub const DEFAULT_MIN_REFERRAL_SHARE_BPS: u16 = 500;  // 5%
+pub const DEFAULT_MAX_REFERRAL_SHARE_BPS: u16 = 5_000; // 50%
+pub const DEFAULT_CREATION_FEE: u64 = LAMPORTS_PER_SOL / 50; // 0.02 SOL, anti-spam
+pub const DEFAULT_CREATION_REFERRAL_SHARE_BPS: u16 = 0;       // 0 = referrer gets nothing on launches
 
 // Graduation 
 pub const DEFAULT_GRADUATION_THRESHOLD: u64 = 85 * LAMPORTS_PER_SOL;
-pub const MIGRATION_FEE: u64 = LAMPORTS_PER_SOL / 2;
+pub const DEFAULT_GRADUATION_MODE: GraduationMode = GraduationMode::RealSol;
+pub const DEFAULT_GRADUATION_MARKET_CAP: u64 = 400 * LAMPORTS_PER_SOL; // ~85 SOL de reserves avec les params par défaut
+pub const DEFAULT_MIGRATION_FEE: MigrationFee = MigrationFee::Fixed { lamports: LAMPORTS_PER_SOL / 2 }; // 0.5 SOL
+pub const DEFAULT_MIGRATION_VENUE: MigrationVenue = MigrationVenue::RaydiumCpmm;
+pub const DEFAULT_MIGRATION_CRANK_REWARD: u64 = LAMPORTS_PER_SOL / 20; // 0.05 SOL pris sur la migration fee, paid to whoever migrates
+pub const DEFAULT_LP_MODE: LpMode = LpMode::Burn;
+pub const DEFAULT_LEFTOVER_MODE: LeftoverMode = LeftoverMode::Burn;
+pub const DEFAULT_MIGRATION_PRICE_TOLERANCE_BPS: u16 = 100; // 1% max between pool opening price and curve price
+pub const CPMM_LOCKED_LIQUIDITY: u128 = 100; // LP cp-swap locks at init, sqrt(amount_0 * amount_1) must exceed it
+pub const DEFAULT_LP_FEE_CREATOR_SHARE_BPS: u16 = 5_000;  // 50% des LP fees, le reste au protocol
+pub const DEFAULT_LP_FEE_REFERRAL_SHARE_BPS: u16 = 0;     // launch referrer, 0 = disabled
+pub const DEFAULT_KEEP_GRADUATION_RECORD: bool = true;
+pub const DEFAULT_FINALIZE_RENT_DESTINATION: RentDestination = RentDestination::Creator; // il a payé la curve au launch
+
+// Scheduled launches
+pub const DEFAULT_MAX_START_DELAY: i64 = 7 * 24 * 60 * 60; // 7 days
+pub const DEFAULT_MAX_PRESALE_DURATION: i64 = 24 * 60 * 60; // 1 day
+
+// Anti-snipe opening window (0 = disabled)
+pub const DEFAULT_OPENING_WINDOW: i64 = 0;
+pub const DEFAULT_MAX_WALLET_BUY_BPS: u16 = 100;   // 1% of total supply
+
+// Creator vesting allocation
+pub const DEFAULT_MAX_CREATOR_ALLOCATION_BPS: u16 = 0;  // la curve par défaut ne laisse que ~0.1% de marge au vault, baisser initial_real_token_reserves pour en ouvrir
+pub const DEFAULT_VESTING_CLIFF: i64 = 30 * 24 * 60 * 60;     // 30 days after graduation
+pub const DEFAULT_VESTING_DURATION: i64 = 180 * 24 * 60 * 60; // 180 days after graduation
+
+// Sunset of inactive curves
+pub const DEFAULT_SUNSET_INACTIVITY: i64 = 90 * 24 * 60 * 60; // 90 days without trades
+pub const DEFAULT_MIGRATION_TIMEOUT: i64 = 7 * 24 * 60 * 60;  // 7 days completed but not migrated
@@ -41,4 +41,136 @@ pub enum TradeError
     InsufficientRentExemption,
     #[msg("Invalid referral account")]
     InvalidReferral,
+    #[msg("Trading has not started yet")]
+    TradingNotStarted,
+    #[msg("Wallet purchase account required during the opening window")]
+    WalletPurchaseRequired,
+    #[msg("Per-wallet buy cap exceeded during the opening window")]
+    WalletCapExceeded,
+    #[msg("Presale is active, use presale_buy")]
+    PresaleActive,
+    #[msg("Presale is not active")]
+    PresaleNotActive,
+    #[msg("Invalid presale proof")]
+    InvalidPresaleProof,
+    #[msg("Presale allocation exceeded")]
+    PresaleCapExceeded,
+    #[msg("Curve has been sunset, redeem instead")]
+    CurveSunset,
+    #[msg("Accounts for this swap route are missing")]
+    SwapAccountsRequired,
+    #[msg("Creator account does not match the curve creator")]
+    InvalidCreatorAccount,
+    #[msg("Pool does not match the migrated curve")]
+    InvalidPool,
+    #[msg("Payment below the curve price")]
+    BelowCurvePrice,
+    #[msg("Transit wSOL account only allowed on a Raydium swap")]
+    UnexpectedWsolAccount,
+}
+
+#[error_code]
+pub enum LaunchError
+{
+    #[msg("Referral share out of bounds")]
+    InvalidReferralShare,
+    #[msg("Start time must be between now and the max start delay")]
+    InvalidStartTime,
+    #[msg("Creator allocation out of bounds")]
+    InvalidCreatorAllocation,
+    #[msg("Vesting accounts required for a creator allocation")]
+    VestingAccountsRequired,
+    #[msg("Presale window out of bounds")]
+    InvalidPresaleWindow,
+    #[msg("Presale can only be set before trading opens")]
+    PresaleLocked,
+    #[msg("Only the creator can do this")]
+    NotCreator,
+    #[msg("Token name is empty or contains invalid characters")]
+    InvalidName,
+    #[msg("Token name too long")]
+    NameTooLong,
+    #[msg("Token symbol must be A-Z / 0-9")]
+    InvalidSymbol,
+    #[msg("Token symbol too long")]
+    SymbolTooLong,
+    #[msg("Token URI contains invalid characters")]
+    InvalidUri,
+    #[msg("Token URI too long")]
+    UriTooLong,
+    #[msg("Token URI must use https, ipfs or ar")]
+    UnsupportedUriScheme,
+    #[msg("Signer is not the pending creator")]
+    NotPendingCreator,
+    #[msg("Token metadata is immutable")]
+    MetadataImmutable,
+    #[msg("Metadata accounts required for a legacy SPL mint")]
+    MetadataAccountsRequired,
+    #[msg("Launch has already been traded")]
+    LaunchAlreadyTraded,
+}
+
+#[error_code]
+pub enum VestingError
+{
+    #[msg("Bonding curve has not graduated yet")]
+    NotGraduated,
+    #[msg("Nothing to claim")]
+    NothingToClaim,
+    #[msg("Only the creator can claim")]
+    NotCreator,
+    #[msg("Bonding curve has not migrated yet")]
+    NotMigrated,
+}
+
+#[error_code]
+pub enum SunsetError
+{
+    #[msg("Sunset is disabled")]
+    SunsetDisabled,
+    #[msg("Curve has traded too recently to be sunset")]
+    CurveStillActive,
+    #[msg("Curve is not sunset")]
+    NotSunset,
+    #[msg("Nothing to redeem")]
+    NothingToRedeem,
+    #[msg("Holders still have tokens to redeem")]
+    RedemptionsOutstanding,
+    #[msg("Curve is still within its migration timeout")]
+    MigrationPending,
+    #[msg("Sells already reopened")]
+    AlreadyReopened,
+}
+
+#[error_code]
+pub enum MigrationError
+{
+    #[msg("Bonding curve has not completed yet")]
+    NotCompleted,
+    #[msg("Bonding curve already migrated")]
+    AlreadyMigrated,
+    #[msg("Migration venue does not match the configured one")]
+    VenueMismatch,
+    #[msg("LP lock accounts required in lock mode")]
+    LpLockAccountsRequired,
+    #[msg("No LP fees to harvest")]
+    NothingToHarvest,
+    #[msg("Pool opening price out of tolerance with the curve price")]
+    PriceOutOfTolerance,
+    #[msg("Leftover token account required to route leftover tokens")]
+    LeftoverAccountRequired,
+    #[msg("Reserves too small to pay the migration fee and crank reward")]
+    ReservesBelowMigrationFee,
+    #[msg("Bonding curve has not migrated yet")]
+    NotMigrated,
+    #[msg("Bonding curve vault still holds tokens")]
+    VaultNotEmpty,
+    #[msg("Graduation record account required")]
+    GraduationRecordRequired,
+    #[msg("Reserves left after the migration fee too small to seed the pool")]
+    PoolDepositTooSmall,
+    #[msg("Graduation record not kept by the config")]
+    GraduationRecordNotKept,
+    #[msg("Curve re-opened for sells, it can only be sunset")]
+    SellsReopened,
 }
@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use crate::state::*;
 
  #[event]
 pub struct TradeEvent
@@ -19,6 +20,9 @@ pub struct CreateEvent
     pub name: String,
     pub symbol: String,
     pub uri: String,
+    pub referral_share_bps: u16,
+    pub start_time: i64,
+    pub creator_allocation: u64,
 }
 
 #[event]
@@ -26,11 +30,182 @@ pub struct CompleteEvent
 {
     pub mint:  Pubkey,
     pub real_sol_reserves:  u64,
+    pub mode: GraduationMode,
+    pub metric: u64,     // real SOL, market cap (lamports) or real tokens left, per mode
+    pub threshold: u64,
 }
 
 #[event]
 pub struct MigrateEvent
 {
   pub mint: Pubkey,
-  pub pool_state: Pubkey,
+  pub venue: MigrationVenue,
+  pub pool: Pubkey,
+  pub caller: Pubkey,
+  pub migration_fee: u64,
+  pub crank_reward: u64,
+  pub sol_amount: u64,
+  pub token_amount: u64,
+  pub leftover_tokens: u64,
+  pub leftover_burned: bool,
+}
+
+#[event]
+pub struct ReferralRegistered
+{
+    pub referrer: Pubkey,
+    pub referral: Pubkey,
+}
+
+#[event]
+pub struct ReferralPaid
+{
+    pub referrer: Pubkey,
+    pub mint: Pubkey,
+    pub trader: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct ReferralClaimed
+{
+    pub referrer: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct ConfigUpdated
+{
+    pub authority: Pubkey,
+    pub old: UpdateConfigParams, // only the changed settings are Some
+    pub new: UpdateConfigParams,
+}
+
+#[event]
+pub struct StatusChanged
+{
+    pub old_status: ProgramStatus,
+    pub new_status: ProgramStatus,
+}
+
+#[event]
+pub struct FeesWithdrawn
+{
+    pub recipient: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct VestingClaimed
+{
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub amount: u64,
+    pub claimed_amount: u64,
+}
+
+#[event]
+pub struct PresaleConfigured
+{
+    pub mint: Pubkey,
+    pub merkle_root: [u8; 32],
+    pub presale_end_time: i64,
+}
+
+#[event]
+pub struct CreatorTransferStarted
+{
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub pending_creator: Option<Pubkey>, // None = nomination cancelled
+}
+
+#[event]
+pub struct CreatorTransferred
+{
+    pub mint: Pubkey,
+    pub old_creator: Pubkey,
+    pub new_creator: Pubkey,
+}
+
+#[event]
+pub struct MetadataUriUpdated
+{
+    pub mint: Pubkey,
+    pub uri: String,
+}
+
+#[event]
+pub struct SupplyLocked
+{
+    pub mint: Pubkey,
+    pub total_supply: u64,
+}
+
+#[event]
+pub struct LaunchCancelled
+{
+    pub mint: Pubkey,   // dead: supply burned, curve closed
+    pub creator: Pubkey,
+    pub burned: u64,
+}
+
+#[event]
+pub struct CurveSunset
+{
+    pub mint: Pubkey,
+    pub real_sol_reserves: u64,
+    pub outstanding_tokens: u64, // held by traders, redeemable against real_sol_reserves
+    pub reason: SunsetReason,
+}
+
+#[event]
+pub struct CurveReopened
+{
+    pub mint: Pubkey,
+    pub completed_at: i64,
+    pub real_sol_reserves: u64,  // what sellers can draw from until migration or sunset
+}
+
+#[event]
+pub struct SunsetRedeemed
+{
+    pub mint: Pubkey,
+    pub holder: Pubkey,
+    pub token_amount: u64,
+    pub sol_amount: u64,
+}
+
+#[event]
+pub struct SunsetCurveClosed
+{
+    pub mint: Pubkey,
+}
+
+#[event]
+pub struct LpLocked
+{
+    pub mint: Pubkey,
+    pub pool_state: Pubkey,
+    pub lp_amount: u64,
+    pub locked_liquidity: u128,
+}
+
+#[event]
+pub struct LpFeesHarvested
+{
+    pub mint: Pubkey,
+    pub lp_withdrawn: u64,
+    pub amount_0: u64,
+    pub amount_1: u64,
+}
+
+#[event]
+pub struct MigrationFinalized
+{
+    pub mint: Pubkey,
+    pub pool: Pubkey,
+    pub rent_receiver: Pubkey,
+    pub rent_amount: u64,          // lamports of the closed curve and vault, record rent excluded
+    pub graduation_record: bool,
 }
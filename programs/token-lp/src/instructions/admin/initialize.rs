@@ -14,7 +14,32 @@ pub fn _initialize(ctx: Context<Initialize>) -> Result<()>
     ctx.accounts.global.trade_fee_bps = DEFAULT_TRADE_FEE_BPS;
     ctx.accounts.global.creator_share_bps = DEFAULT_CREATOR_SHARE_BPS;
     ctx.accounts.global.referral_share_bps = DEFAULT_REFERRAL_SHARE_BPS;
+    ctx.accounts.global.min_referral_share_bps = DEFAULT_MIN_REFERRAL_SHARE_BPS;
+    ctx.accounts.global.max_referral_share_bps = DEFAULT_MAX_REFERRAL_SHARE_BPS;
+    ctx.accounts.global.creation_fee = DEFAULT_CREATION_FEE;
+    ctx.accounts.global.creation_referral_share_bps = DEFAULT_CREATION_REFERRAL_SHARE_BPS;
     ctx.accounts.global.graduation_threshold = DEFAULT_GRADUATION_THRESHOLD;
+    ctx.accounts.global.graduation_mode = DEFAULT_GRADUATION_MODE;
+    ctx.accounts.global.graduation_market_cap = DEFAULT_GRADUATION_MARKET_CAP;
+    ctx.accounts.global.migration_fee = DEFAULT_MIGRATION_FEE;
+    ctx.accounts.global.migration_crank_reward = DEFAULT_MIGRATION_CRANK_REWARD;
+    ctx.accounts.global.migration_venue = DEFAULT_MIGRATION_VENUE;
+    ctx.accounts.global.lp_mode = DEFAULT_LP_MODE;
+    ctx.accounts.global.lp_fee_creator_share_bps = DEFAULT_LP_FEE_CREATOR_SHARE_BPS;
+    ctx.accounts.global.lp_fee_referral_share_bps = DEFAULT_LP_FEE_REFERRAL_SHARE_BPS;
+    ctx.accounts.global.leftover_mode = DEFAULT_LEFTOVER_MODE;
+    ctx.accounts.global.migration_price_tolerance_bps = DEFAULT_MIGRATION_PRICE_TOLERANCE_BPS;
+    ctx.accounts.global.keep_graduation_record = DEFAULT_KEEP_GRADUATION_RECORD;
+    ctx.accounts.global.finalize_rent_destination = DEFAULT_FINALIZE_RENT_DESTINATION;
+    ctx.accounts.global.max_start_delay = DEFAULT_MAX_START_DELAY;
+    ctx.accounts.global.max_presale_duration = DEFAULT_MAX_PRESALE_DURATION;
+    ctx.accounts.global.opening_window = DEFAULT_OPENING_WINDOW;
+    ctx.accounts.global.max_wallet_buy_bps = DEFAULT_MAX_WALLET_BUY_BPS;
+    ctx.accounts.global.max_creator_allocation_bps = DEFAULT_MAX_CREATOR_ALLOCATION_BPS;
+    ctx.accounts.global.vesting_cliff = DEFAULT_VESTING_CLIFF;
+    ctx.accounts.global.vesting_duration = DEFAULT_VESTING_DURATION;
+    ctx.accounts.global.sunset_inactivity = DEFAULT_SUNSET_INACTIVITY;
+    ctx.accounts.global.migration_timeout = DEFAULT_MIGRATION_TIMEOUT;
     ctx.accounts.global.status = ProgramStatus::Running;
     ctx.accounts.global.bump = ctx.bumps.global;
 
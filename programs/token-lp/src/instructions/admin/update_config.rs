@@ -2,82 +2,305 @@ use anchor_lang::prelude::*;
 use crate::constants::*;
 use crate::state::*;
 use crate::errors::*;
+use crate::events::*;
+use crate::instructions::trade::graduation::{graduation_reserves, max_creator_allocation};
 
-pub fn _update_config(ctx: Context<UpdateConfig>,
-    new_fee_receiver: Option<Pubkey>,
-    new_initial_virtual_sol_reserves: Option<u64>,
-    new_initial_virtual_token_reserves: Option<u64>,
-    new_initial_real_token_reserves: Option<u64>,
-    new_token_total_supply: Option<u64>,
-    new_trade_fee_bps: Option<u16>,
-    new_creator_share_bps: Option<u16>,
-    new_referral_share_bps: Option<u16>,
-    new_graduation_threshold: Option<u64>,
-    new_status: Option<ProgramStatus>,
-    ) -> Result<()>
+pub fn _update_config(ctx: Context<UpdateConfig>, params: UpdateConfigParams) -> Result<()>
 {
-    if let Some(val) = new_fee_receiver
+    let old_config = Global::clone(&ctx.accounts.global);
+
+    if let Some(val) = params.fee_receiver
     {
         ctx.accounts.global.fee_receiver = val;
     }
 
-    if let Some(val) = new_initial_virtual_sol_reserves
+    if let Some(val) = params.initial_virtual_sol_reserves
     {
         require!(val > 0, AdminError::InvalidConfigParam);
         ctx.accounts.global.initial_virtual_sol_reserves = val;
     }
 
-    if let Some(val) = new_initial_virtual_token_reserves
+    if let Some(val) = params.initial_virtual_token_reserves
     {
         require!(val > 0, AdminError::InvalidConfigParam);
         ctx.accounts.global.initial_virtual_token_reserves = val;
     }
 
-    if let Some(val) = new_initial_real_token_reserves
+    if let Some(val) = params.initial_real_token_reserves
     {
         require!(val > 0, AdminError::InvalidConfigParam);
         ctx.accounts.global.initial_real_token_reserves = val;
     }
 
-    if let Some(val) = new_token_total_supply
+    if let Some(val) = params.token_total_supply
     {
         require!(val > 0, AdminError::InvalidConfigParam);
         ctx.accounts.global.token_total_supply = val;
     }
 
-    if let Some(val) = new_trade_fee_bps
+    if let Some(val) = params.trade_fee_bps
     {
         require!(val <= 5_000, AdminError::InvalidConfigParam); // max 50%
         ctx.accounts.global.trade_fee_bps = val;
     }
 
-    if let Some(val) = new_creator_share_bps
+    if let Some(val) = params.creator_share_bps
     {
+        require!(val <= 10_000, AdminError::InvalidConfigParam);
         ctx.accounts.global.creator_share_bps = val;
-        // validate combined shares
-        require!(ctx.accounts.global.creator_share_bps as u32 + ctx.accounts.global.referral_share_bps as u32 <= 10_000, AdminError::InvalidConfigParam);
     }
 
-    if let Some(val) = new_referral_share_bps
+    if let Some(val) = params.referral_share_bps
     {
         ctx.accounts.global.referral_share_bps = val;
-        // validate combined shares
-        require!(ctx.accounts.global.creator_share_bps as u32 + ctx.accounts.global.referral_share_bps as u32 <= 10_000, AdminError::InvalidConfigParam);
     }
 
-    if let Some(val) = new_graduation_threshold
+    if let Some(val) = params.min_referral_share_bps
+    {
+        ctx.accounts.global.min_referral_share_bps = val;
+    }
+
+    if let Some(val) = params.max_referral_share_bps
+    {
+        ctx.accounts.global.max_referral_share_bps = val;
+    }
+
+    if let Some(val) = params.max_start_delay
+    {
+        require!(val >= 0, AdminError::InvalidConfigParam);
+        ctx.accounts.global.max_start_delay = val;
+    }
+
+    if let Some(val) = params.opening_window
+    {
+        require!(val >= 0, AdminError::InvalidConfigParam);
+        ctx.accounts.global.opening_window = val;
+    }
+
+    if let Some(val) = params.max_wallet_buy_bps
+    {
+        require!(val > 0 && val <= 10_000, AdminError::InvalidConfigParam);
+        ctx.accounts.global.max_wallet_buy_bps = val;
+    }
+
+    if let Some(val) = params.max_creator_allocation_bps
+    {
+        require!(val <= 10_000, AdminError::InvalidConfigParam);
+        ctx.accounts.global.max_creator_allocation_bps = val;
+    }
+
+    if let Some(val) = params.vesting_cliff
+    {
+        require!(val >= 0, AdminError::InvalidConfigParam);
+        ctx.accounts.global.vesting_cliff = val;
+    }
+
+    if let Some(val) = params.vesting_duration
+    {
+        require!(val >= 0, AdminError::InvalidConfigParam);
+        ctx.accounts.global.vesting_duration = val;
+    }
+
+    if let Some(val) = params.max_presale_duration
+    {
+        require!(val >= 0, AdminError::InvalidConfigParam);
+        ctx.accounts.global.max_presale_duration = val;
+    }
+
+    if let Some(val) = params.creation_fee
+    {
+        ctx.accounts.global.creation_fee = val;
+    }
+
+    if let Some(val) = params.creation_referral_share_bps
+    {
+        require!(val <= 10_000, AdminError::InvalidConfigParam);
+        ctx.accounts.global.creation_referral_share_bps = val;
+    }
+
+    if let Some(val) = params.sunset_inactivity
+    {
+        require!(val >= 0, AdminError::InvalidConfigParam);
+        ctx.accounts.global.sunset_inactivity = val;
+    }
+
+    if let Some(val) = params.migration_timeout
+    {
+        require!(val >= 0, AdminError::InvalidConfigParam);
+        ctx.accounts.global.migration_timeout = val;
+    }
+
+    if let Some(val) = params.graduation_threshold
     {
         require!(val > 0, AdminError::InvalidConfigParam);
         ctx.accounts.global.graduation_threshold = val;
     }
 
-    if let Some(val) = new_status
+    if let Some(val) = params.graduation_mode
     {
-            ctx.accounts.global.status = val;
+        ctx.accounts.global.graduation_mode = val;
     }
+
+    if let Some(val) = params.graduation_market_cap
+    {
+        require!(val > 0, AdminError::InvalidConfigParam);
+        ctx.accounts.global.graduation_market_cap = val;
+    }
+
+    if let Some(val) = params.migration_fee
+    {
+        if let MigrationFee::Bps { bps } = val
+        {
+            require!(bps < 10_000, AdminError::InvalidConfigParam);
+        }
+        ctx.accounts.global.migration_fee = val;
+    }
+
+    if let Some(val) = params.migration_crank_reward
+    {
+        ctx.accounts.global.migration_crank_reward = val;
+    }
+
+    if let Some(val) = params.migration_venue
+    {
+        ctx.accounts.global.migration_venue = val;
+    }
+
+    if let Some(val) = params.lp_mode
+    {
+        ctx.accounts.global.lp_mode = val;
+    }
+
+    if let Some(val) = params.lp_fee_creator_share_bps
+    {
+        ctx.accounts.global.lp_fee_creator_share_bps = val;
+    }
+
+    if let Some(val) = params.lp_fee_referral_share_bps
+    {
+        ctx.accounts.global.lp_fee_referral_share_bps = val;
+    }
+
+    if let Some(val) = params.leftover_mode
+    {
+        ctx.accounts.global.leftover_mode = val;
+    }
+
+    if let Some(val) = params.migration_price_tolerance_bps
+    {
+        require!(val <= 10_000, AdminError::InvalidConfigParam);
+        ctx.accounts.global.migration_price_tolerance_bps = val;
+    }
+
+    if let Some(val) = params.keep_graduation_record
+    {
+        ctx.accounts.global.keep_graduation_record = val;
+    }
+
+    if let Some(val) = params.finalize_rent_destination
+    {
+        ctx.accounts.global.finalize_rent_destination = val;
+    }
+
+    if let Some(val) = params.status
+    {
+        if val != ctx.accounts.global.status
+        {
+            emit!(StatusChanged {
+                old_status: ctx.accounts.global.status,
+                new_status: val,
+            });
+        }
+        ctx.accounts.global.status = val;
+    }
+
+    // referral is paid out of the creator share: min <= default <= max <= creator
+    let global = &ctx.accounts.global;
+    require!(global.min_referral_share_bps <= global.referral_share_bps, AdminError::InvalidConfigParam);
+    require!(global.referral_share_bps <= global.max_referral_share_bps, AdminError::InvalidConfigParam);
+    require!(global.max_referral_share_bps <= global.creator_share_bps, AdminError::InvalidConfigParam);
+    require!(global.vesting_cliff <= global.vesting_duration, AdminError::InvalidConfigParam);
+    // the leanest curve the graduation mode completes must be able to pay the migration fee, crank reward included
+    let (graduation_sol, _, _) = graduation_reserves(global).map_err(|_| AdminError::InvalidConfigParam)?;
+    let migration_fee = global.migration_fee_for(graduation_sol).ok_or(MathError::Overflow)?;
+    require!(migration_fee < graduation_sol, AdminError::InvalidConfigParam);
+    require!(global.migration_crank_reward <= migration_fee, AdminError::InvalidConfigParam);
+    // the largest allocation must leave the vault enough tokens to pair a sold-out curve at its price
+    let max_allocation = (global.token_total_supply as u128)
+        .checked_mul(global.max_creator_allocation_bps as u128)
+        .ok_or(MathError::Overflow)?
+        .checked_div(10_000)
+        .ok_or(MathError::DivisionByZero)?;
+    require!(max_allocation <= max_creator_allocation(global)? as u128, AdminError::InvalidConfigParam);
+    require!((global.lp_fee_creator_share_bps as u32) + (global.lp_fee_referral_share_bps as u32) <= 10_000, AdminError::InvalidConfigParam);
+
+    let (old, new) = config_diff(&old_config, global);
+    emit!(ConfigUpdated {
+        authority: ctx.accounts.authority.key(),
+        old,
+        new,
+    });
     Ok(())
 }
 
+/// Settings that actually changed, as (old values, new values); unchanged ones stay None
+fn config_diff(old: &Global, new: &Global) -> (UpdateConfigParams, UpdateConfigParams)
+{
+    let mut before = UpdateConfigParams::default();
+    let mut after = UpdateConfigParams::default();
+
+    macro_rules! diff {
+        ($($field:ident),* $(,)?) => {
+            $(
+                if old.$field != new.$field
+                {
+                    before.$field = Some(old.$field);
+                    after.$field = Some(new.$field);
+                }
+            )*
+        };
+    }
+    diff!(
+        fee_receiver,
+        initial_virtual_sol_reserves,
+        initial_virtual_token_reserves,
+        initial_real_token_reserves,
+        token_total_supply,
+        trade_fee_bps,
+        creator_share_bps,
+        referral_share_bps,
+        graduation_threshold,
+        status,
+        min_referral_share_bps,
+        max_referral_share_bps,
+        max_start_delay,
+        opening_window,
+        max_wallet_buy_bps,
+        max_creator_allocation_bps,
+        vesting_cliff,
+        vesting_duration,
+        max_presale_duration,
+        creation_fee,
+        creation_referral_share_bps,
+        sunset_inactivity,
+        migration_crank_reward,
+        migration_venue,
+        lp_mode,
+        lp_fee_creator_share_bps,
+        lp_fee_referral_share_bps,
+        leftover_mode,
+        migration_price_tolerance_bps,
+        migration_fee,
+        keep_graduation_record,
+        finalize_rent_destination,
+        graduation_mode,
+        graduation_market_cap,
+        migration_timeout,
+    );
+
+    (before, after)
+}
+
 
 #[derive(Accounts)]
 pub struct UpdateConfig<'info>
@@ -2,6 +2,7 @@ use anchor_lang::prelude::*;
 use crate::constants::*;
 use crate::state::*;
 use crate::errors::*;
+use crate::events::*;
 
 pub fn _withdraw_fees(ctx: Context<WithdrawFees>) -> Result<()>
 {
@@ -22,6 +23,11 @@ pub fn _withdraw_fees(ctx: Context<WithdrawFees>) -> Result<()>
         signer,
     );
     anchor_lang::system_program::transfer(cpi_ctx, amount)?;
+
+    emit!(FeesWithdrawn {
+        recipient: ctx.accounts.recipient.key(),
+        amount,
+    });
     Ok(())
 }
 
@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::events::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Step 2: the nominated wallet takes over; creator fees, presale, vesting and locked LP fee rights follow.
+pub fn _accept_creator(ctx: Context<AcceptCreator>) -> Result<()>
+{
+    let bc = &mut ctx.accounts.bonding_curve;
+    let old_creator = bc.creator;
+
+    bc.creator = ctx.accounts.new_creator.key();
+    bc.pending_creator = None;
+
+    // A migrated curve may have locked its LP: the harvested fees follow the creator too
+    if let Some(lp_lock) = ctx.accounts.lp_lock.as_mut()
+    {
+        lp_lock.creator = bc.creator;
+    }
+
+    emit!(CreatorTransferred {
+        mint: bc.mint,
+        old_creator,
+        new_creator: bc.creator,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AcceptCreator<'info>
+{
+    pub new_creator: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [BONDING_CURVE_SEED, bonding_curve.mint.as_ref()],
+        bump,
+        constraint = bonding_curve.pending_creator == Some(new_creator.key()) @ LaunchError::NotPendingCreator,
+    )]
+    pub bonding_curve: Account<'info, BondingCurve>,
+
+    /// Pass it once migrate_to_raydium locked the LP, otherwise its fees keep going to the previous creator
+    #[account(
+        mut,
+        seeds = [LP_LOCK_SEED, bonding_curve.mint.as_ref()],
+        bump = lp_lock.bump,
+    )]
+    pub lp_lock: Option<Account<'info, LpLock>>,
+}
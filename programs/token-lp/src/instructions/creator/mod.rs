@@ -0,0 +1,7 @@
+pub mod transfer_creator;
+pub mod accept_creator;
+pub mod update_token_uri;
+
+pub use transfer_creator::*;
+pub use accept_creator::*;
+pub use update_token_uri::*;
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::events::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Step 1: the current creator nominates a new creator, who must accept.
+/// Calling it again replaces the pending nomination; nominating the creator itself cancels it.
+pub fn _transfer_creator(ctx: Context<TransferCreator>, new_creator: Pubkey) -> Result<()>
+{
+    let bc = &mut ctx.accounts.bonding_curve;
+
+    bc.pending_creator = if new_creator == bc.creator { None } else { Some(new_creator) };
+
+    emit!(CreatorTransferStarted {
+        mint: bc.mint,
+        creator: bc.creator,
+        pending_creator: bc.pending_creator,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct TransferCreator<'info>
+{
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [BONDING_CURVE_SEED, bonding_curve.mint.as_ref()],
+        bump,
+        constraint = bonding_curve.creator == creator.key() @ LaunchError::NotCreator,
+    )]
+    pub bonding_curve: Account<'info, BondingCurve>,
+}
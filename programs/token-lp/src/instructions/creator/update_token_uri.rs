@@ -0,0 +1,202 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::events::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::utils::validation::validate_uri;
+use anchor_spl::metadata::{
+    update_metadata_accounts_v2,
+    UpdateMetadataAccountsV2,
+    Metadata,
+    MetadataAccount,
+    mpl_token_metadata::types::DataV2,
+};
+use anchor_spl::token_interface::{
+    Mint,
+    TokenInterface,
+    token_metadata_update_field,
+    TokenMetadataUpdateField,
+    token_metadata_update_authority,
+    TokenMetadataUpdateAuthority,
+    spl_pod::optional_keys::OptionalNonZeroPubkey,
+    spl_token_2022::extension::{BaseStateWithExtensions, StateWithExtensions},
+    spl_token_metadata_interface::state::{Field, TokenMetadata},
+};
+
+/// Only for launches created with mutable metadata, and only until the curve completes.
+pub fn _update_token_uri(ctx: Context<UpdateTokenUri>, uri: String) -> Result<()>
+{
+    require!(ctx.accounts.bonding_curve.metadata_mutable, LaunchError::MetadataImmutable);
+    let uri = validate_uri(&uri)?;
+
+    let mint_key = ctx.accounts.mint.key();
+    let seeds = &[
+        BONDING_CURVE_SEED,
+        mint_key.as_ref(),
+        &[ctx.accounts.bonding_curve.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    if ctx.accounts.mint.to_account_info().owner == &anchor_spl::token_2022::ID
+    {
+        // A longer URI grows the mint account: top up its rent first
+        let mint_info = ctx.accounts.mint.to_account_info();
+        let (old_size, new_size) = {
+            let data = mint_info.try_borrow_data()?;
+            let state = StateWithExtensions::<anchor_spl::token_interface::spl_token_2022::state::Mint>::unpack(&data)?;
+            let mut token_metadata = state.get_variable_len_extension::<TokenMetadata>()?;
+            let old_size = token_metadata.tlv_size_of()?;
+            token_metadata.uri = uri.clone();
+            (old_size, token_metadata.tlv_size_of()?)
+        };
+        let new_len = mint_info.data_len().checked_add(new_size).ok_or(MathError::Overflow)?.saturating_sub(old_size);
+        let rent_top_up = Rent::get()?.minimum_balance(new_len).saturating_sub(mint_info.lamports());
+
+        if rent_top_up > 0
+        {
+            anchor_lang::system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: ctx.accounts.creator.to_account_info(),
+                        to: mint_info,
+                    },
+                ),
+                rent_top_up,
+            )?;
+        }
+
+        token_metadata_update_field(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TokenMetadataUpdateField {
+                    program_id: ctx.accounts.token_program.to_account_info(),
+                    metadata: ctx.accounts.mint.to_account_info(),
+                    update_authority: ctx.accounts.bonding_curve.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            Field::Uri,
+            uri.clone(),
+        )?;
+    }
+    else
+    {
+        let (Some(metadata), Some(token_metadata_program)) = (&ctx.accounts.metadata, &ctx.accounts.token_metadata_program)
+        else {
+            return err!(LaunchError::MetadataAccountsRequired);
+        };
+
+        update_metadata_accounts_v2(
+            CpiContext::new_with_signer(
+                token_metadata_program.to_account_info(),
+                UpdateMetadataAccountsV2 {
+                    metadata: metadata.to_account_info(),
+                    update_authority: ctx.accounts.bonding_curve.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            None,
+            Some(DataV2 {
+                name: metadata.name.clone(),
+                symbol: metadata.symbol.clone(),
+                uri: uri.clone(),
+                seller_fee_basis_points: metadata.seller_fee_basis_points,
+                creators: metadata.creators.clone(),
+                collection: metadata.collection.clone(),
+                uses: metadata.uses.clone(),
+            }),
+            None,
+            None,
+        )?;
+    }
+
+    emit!(MetadataUriUpdated {
+        mint: mint_key,
+        uri,
+    });
+    Ok(())
+}
+
+/// Makes the token metadata immutable, signed by the bonding curve PDA:
+/// is_mutable = false on Metaplex, update authority revoked on Token-2022.
+pub fn lock_metadata<'info>(
+    bonding_curve: AccountInfo<'info>,
+    mint: AccountInfo<'info>,
+    mint_token_program: AccountInfo<'info>,
+    metadata: Option<AccountInfo<'info>>,
+    token_metadata_program: Option<AccountInfo<'info>>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()>
+{
+    if mint.owner == &anchor_spl::token_2022::ID
+    {
+        token_metadata_update_authority(
+            CpiContext::new_with_signer(
+                mint_token_program.clone(),
+                TokenMetadataUpdateAuthority {
+                    program_id: mint_token_program,
+                    metadata: mint,
+                    current_authority: bonding_curve.clone(),
+                    new_authority: bonding_curve,
+                },
+                signer_seeds,
+            ),
+            OptionalNonZeroPubkey::default(),
+        )
+    }
+    else
+    {
+        let (Some(metadata), Some(token_metadata_program)) = (metadata, token_metadata_program)
+        else {
+            return err!(LaunchError::MetadataAccountsRequired);
+        };
+
+        update_metadata_accounts_v2(
+            CpiContext::new_with_signer(
+                token_metadata_program,
+                UpdateMetadataAccountsV2 {
+                    metadata,
+                    update_authority: bonding_curve,
+                },
+                signer_seeds,
+            ),
+            None,
+            None,
+            None,
+            Some(false), // is_mutable
+        )
+    }
+}
+
+#[derive(Accounts)]
+pub struct UpdateTokenUri<'info>
+{
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        seeds = [BONDING_CURVE_SEED, mint.key().as_ref()],
+        bump,
+        constraint = bonding_curve.creator == creator.key() @ LaunchError::NotCreator,
+        constraint = bonding_curve.completed == false @ TradeError::CurveCompleted,
+    )]
+    pub bonding_curve: Account<'info, BondingCurve>,
+
+    #[account(
+        mut,
+        mint::token_program = token_program,
+    )]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Metaplex metadata, legacy SPL mints only
+    #[account(
+        mut,
+        constraint = metadata.mint == mint.key(),
+    )]
+    pub metadata: Option<Box<Account<'info, MetadataAccount>>>,
+
+    pub token_metadata_program: Option<Program<'info, Metadata>>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
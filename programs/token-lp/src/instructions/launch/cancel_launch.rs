@@ -0,0 +1,150 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::events::*;
+use crate::state::*;
+use crate::errors::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+/// Undo a launch nobody traded: burn the supply, close the vault (and vesting) accounts
+/// and the bonding curve, rent back to the creator. The mint itself stays, dead with zero
+/// supply, and so does a Metaplex metadata account (the metadata program can't close it).
+pub fn _cancel_launch(ctx: Context<CancelLaunch>) -> Result<()>
+{
+    let bc = &ctx.accounts.bonding_curve;
+    let curve_supply = bc.token_total_supply.checked_sub(bc.creator_allocation).ok_or(MathError::Overflow)?;
+    require!(bc.real_sol_reserves == 0 && ctx.accounts.token_account.amount == curve_supply, LaunchError::LaunchAlreadyTraded);
+
+    let mint_key = ctx.accounts.mint.key();
+    let seeds = &[
+        BONDING_CURVE_SEED,
+        mint_key.as_ref(),
+        &[bc.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    anchor_spl::token_interface::burn(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            anchor_spl::token_interface::Burn {
+                mint: ctx.accounts.mint.to_account_info(),
+                from: ctx.accounts.token_account.to_account_info(),
+                authority: ctx.accounts.bonding_curve.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        curve_supply,
+    )?;
+
+    anchor_spl::token_interface::close_account(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            anchor_spl::token_interface::CloseAccount {
+                account: ctx.accounts.token_account.to_account_info(),
+                destination: ctx.accounts.creator.to_account_info(),
+                authority: ctx.accounts.bonding_curve.to_account_info(),
+            },
+            signer_seeds,
+        ),
+    )?;
+
+    let mut burned = curve_supply;
+
+    if bc.creator_allocation > 0
+    {
+        let (Some(vesting), Some(vesting_token_account)) = (&ctx.accounts.vesting, &ctx.accounts.vesting_token_account)
+        else {
+            return err!(LaunchError::VestingAccountsRequired);
+        };
+
+        let vesting_seeds = &[
+            VESTING_SEED,
+            mint_key.as_ref(),
+            &[vesting.bump],
+        ];
+        let vesting_signer_seeds = &[&vesting_seeds[..]];
+
+        anchor_spl::token_interface::burn(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                anchor_spl::token_interface::Burn {
+                    mint: ctx.accounts.mint.to_account_info(),
+                    from: vesting_token_account.to_account_info(),
+                    authority: vesting.to_account_info(),
+                },
+                vesting_signer_seeds,
+            ),
+            vesting_token_account.amount,
+        )?;
+
+        anchor_spl::token_interface::close_account(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                anchor_spl::token_interface::CloseAccount {
+                    account: vesting_token_account.to_account_info(),
+                    destination: ctx.accounts.creator.to_account_info(),
+                    authority: vesting.to_account_info(),
+                },
+                vesting_signer_seeds,
+            ),
+        )?;
+
+        burned = burned.checked_add(vesting_token_account.amount).ok_or(MathError::Overflow)?;
+    }
+
+    emit!(LaunchCancelled {
+        mint: mint_key,
+        creator: ctx.accounts.creator.key(),
+        burned,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CancelLaunch<'info>
+{
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        mint::token_program = token_program,
+    )]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        close = creator,
+        seeds = [BONDING_CURVE_SEED, mint.key().as_ref()],
+        bump,
+        constraint = bonding_curve.creator == creator.key() @ LaunchError::NotCreator,
+        constraint = bonding_curve.completed == false @ TradeError::CurveCompleted,
+    )]
+    pub bonding_curve: Account<'info, BondingCurve>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = bonding_curve,
+        associated_token::token_program = token_program,
+    )]
+    pub token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Only needed with a creator allocation
+    #[account(
+        mut,
+        close = creator,
+        seeds = [VESTING_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub vesting: Option<Box<Account<'info, CreatorVesting>>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vesting,
+        associated_token::token_program = token_program,
+    )]
+    pub vesting_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
@@ -1,9 +1,11 @@
 use anchor_lang::prelude::*;
 use crate::events::*;
 use crate::constants::*;
-use crate::utils::math::calculate_buy_amount;
 use crate::state::*;
 use crate::errors::*;
+use super::create_token::{check_launch, init_bonding_curve, pay_creation_fee, LaunchTerms};
+use crate::instructions::trade::graduation::check_graduation;
+use crate::instructions::trade::curve::{quote_buy, track_wallet_purchase, pay_trade_fee, FeeSource};
 use anchor_spl::token::{Mint, TokenAccount, Token};
 use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::metadata::{
@@ -13,22 +15,23 @@ use anchor_spl::metadata::{
     mpl_token_metadata::types::DataV2,
 };
 
-pub fn _create_and_buy_token(ctx: Context<CreateAndBuyToken>, name: String, symbol: String, uri: String, sol_amount: u64, min_tokens_out: u64) -> Result<()>
+pub fn _create_and_buy_token(ctx: Context<CreateAndBuyToken>, name: String, symbol: String, uri: String, sol_amount: u64, min_tokens_out: u64, referral_share_bps: Option<u16>, start_time: Option<i64>, creator_allocation_bps: Option<u16>, mutable_metadata: Option<bool>) -> Result<()>
 {
-    require!(ctx.accounts.global.status == ProgramStatus::Running, AdminError::ProgramPaused);
+    let (name, symbol, uri, launch) = check_launch(&ctx.accounts.global, &name, &symbol, &uri, referral_share_bps, start_time, creator_allocation_bps, mutable_metadata)?;
+    let LaunchTerms { referral_share_bps, start_time, creator_allocation, curve_supply, mutable_metadata } = launch;
+
+    let launch_referrer = pay_creation_fee(
+        &ctx.accounts.global,
+        &ctx.accounts.creator,
+        &ctx.accounts.fee_vault,
+        ctx.accounts.referral.as_mut(),
+        &ctx.accounts.system_program,
+        ctx.accounts.mint.key(),
+        ctx.program_id,
+    )?;
 
     let bc = &mut ctx.accounts.bonding_curve;
-    bc.mint = ctx.accounts.mint.key();
-    bc.creator = ctx.accounts.creator.key();
-    bc.virtual_sol = ctx.accounts.global.initial_virtual_sol_reserves;
-    bc.virtual_token = ctx.accounts.global.initial_virtual_token_reserves;
-    bc.real_token = ctx.accounts.global.initial_real_token_reserves;
-    bc.real_sol_reserves = 0;
-    bc.token_total_supply = ctx.accounts.global.token_total_supply;
-    bc.start_time = Clock::get()?.unix_timestamp;
-    bc.completed = false;
-    bc.migrated = false;
-    bc.bump = ctx.bumps.bonding_curve;
+    init_bonding_curve(bc, &ctx.accounts.global, ctx.accounts.mint.key(), ctx.accounts.creator.key(), &launch, launch_referrer, ctx.bumps.bonding_curve);
 
     let mint_key = ctx.accounts.mint.key();
     let seeds = &[
@@ -48,9 +51,36 @@ pub fn _create_and_buy_token(ctx: Context<CreateAndBuyToken>, name: String, symb
             },
             signer_seeds,
         ),
-        ctx.accounts.global.token_total_supply,
+        curve_supply,
     )?;
 
+    if creator_allocation > 0
+    {
+        let (Some(vesting), Some(vesting_token_account)) = (&mut ctx.accounts.vesting, &ctx.accounts.vesting_token_account)
+        else {
+            return err!(LaunchError::VestingAccountsRequired);
+        };
+        vesting.mint = ctx.accounts.mint.key();
+        vesting.total_amount = creator_allocation;
+        vesting.claimed_amount = 0;
+        vesting.cliff = ctx.accounts.global.vesting_cliff;
+        vesting.duration = ctx.accounts.global.vesting_duration;
+        vesting.bump = ctx.bumps.vesting.ok_or(LaunchError::VestingAccountsRequired)?;
+
+        anchor_spl::token::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                anchor_spl::token::MintTo {
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: vesting_token_account.to_account_info(),
+                    authority: ctx.accounts.bonding_curve.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            creator_allocation,
+        )?;
+    }
+
     // Revoke freeze authority
     anchor_spl::token::set_authority(
         CpiContext::new_with_signer(
@@ -93,11 +123,26 @@ pub fn _create_and_buy_token(ctx: Context<CreateAndBuyToken>, name: String, symb
             collection: None,
             uses: None,
         },
-        false, // is_mutable
+        mutable_metadata, // is_mutable
         true,  // update_authority_is_signer
         None,  // collection_details
     )?;
 
+    // Revoke mint authority: the whole supply is minted. Done after the Metaplex CPI, which needs it as signer
+    anchor_spl::token::set_authority(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            anchor_spl::token::SetAuthority {
+                account_or_mint: ctx.accounts.mint.to_account_info(),
+                current_authority: ctx.accounts.bonding_curve.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        anchor_spl::token::spl_token::instruction::AuthorityType::MintTokens,
+        None,
+    )?;
+    ctx.accounts.bonding_curve.supply_locked = true;
+
     // Emit create event first (before trade) for correct indexer ordering
     emit!(CreateEvent{
         creator: ctx.accounts.creator.key(),
@@ -105,26 +150,37 @@ pub fn _create_and_buy_token(ctx: Context<CreateAndBuyToken>, name: String, symb
         name: ev_name,
         symbol: ev_symbol,
         uri: ev_uri,
+        referral_share_bps,
+        start_time,
+        creator_allocation,
     });
 
-    //BUY
-
-    require!(sol_amount > 0, TradeError::ZeroAmount);
+    emit!(SupplyLocked {
+        mint: ctx.accounts.mint.key(),
+        total_supply: ctx.accounts.bonding_curve.token_total_supply,
+    });
 
-    let fee = (sol_amount as u128)
-        .checked_mul(ctx.accounts.global.trade_fee_bps as u128)
-        .ok_or(MathError::Overflow)?
-        .checked_div(10_000)
-        .ok_or(MathError::DivisionByZero)?;
-    let fee = u64::try_from(fee).map_err(|_| MathError::CastOverflow)?;
+    //BUY — the creator's anti-snipe buy is allowed before start_time
 
-    let sol_after_fee = sol_amount.checked_sub(fee).ok_or(MathError::Overflow)?;
+    require!(sol_amount > 0, TradeError::ZeroAmount);
 
-    let tokens_out = calculate_buy_amount(ctx.accounts.bonding_curve.virtual_sol, ctx.accounts.bonding_curve.virtual_token, sol_after_fee)?;
+    // Same pricing as any curve buy, TokensSold clipping included
+    let (fee, sol_after_fee, tokens_out) = quote_buy(&ctx.accounts.global, &ctx.accounts.bonding_curve, sol_amount)?;
 
     require!(tokens_out >= min_tokens_out, TradeError::SlippageExceeded);
     require!(tokens_out <= ctx.accounts.bonding_curve.real_token, TradeError::NotEnoughTokens);
 
+    // Same per-wallet cap as any other buy: the bundled buy counts toward the creator's opening window
+    track_wallet_purchase(
+        &ctx.accounts.global,
+        &ctx.accounts.bonding_curve,
+        ctx.accounts.wallet_purchase.as_mut(),
+        ctx.bumps.wallet_purchase,
+        ctx.accounts.creator.key(),
+        ctx.accounts.mint.key(),
+        tokens_out,
+    )?;
+
     let cpi_context = CpiContext::new(
         ctx.accounts.system_program.to_account_info(),
         anchor_lang::system_program::Transfer{
@@ -154,80 +210,28 @@ pub fn _create_and_buy_token(ctx: Context<CreateAndBuyToken>, name: String, symb
 
     anchor_spl::token::transfer(cpi_context, tokens_out)?;
 
-    // Creator fee — skip transfer since creator == signer (would pay themselves)
-    let creator_fee = (fee as u128)
-        .checked_mul(ctx.accounts.global.creator_share_bps as u128)
-        .ok_or(MathError::Overflow)?
-        .checked_div(10_000)
-        .ok_or(MathError::DivisionByZero)?;
-    let creator_fee = u64::try_from(creator_fee).map_err(|_| MathError::CastOverflow)?;
-    let remaining_fee = fee.checked_sub(creator_fee).ok_or(MathError::Overflow)?;
-
-    // Fee distribution with referral split
-    if let Some(referral) = &mut ctx.accounts.referral
-    {
-        // Validate referral PDA
-        let (expected_pda, _) = Pubkey::find_program_address(
-            &[REFERRAL_SEED, referral.referrer.as_ref()],
-            ctx.program_id,
-        );
-        require!(referral.key() == expected_pda, TradeError::InvalidReferral);
-
-        let referral_fee = (remaining_fee as u128)
-            .checked_mul(ctx.accounts.global.referral_share_bps as u128)
-            .ok_or(MathError::Overflow)?
-            .checked_div(10_000)
-            .ok_or(MathError::DivisionByZero)?;
-        let referral_fee = u64::try_from(referral_fee).map_err(|_| MathError::CastOverflow)?;
-
-        let protocol_fee = remaining_fee.checked_sub(referral_fee).ok_or(MathError::Overflow)?;
-
-        let cpi_context = CpiContext::new(
-            ctx.accounts.system_program.to_account_info(),
-            anchor_lang::system_program::Transfer{
-                from: ctx.accounts.creator.to_account_info(),
-                to: ctx.accounts.fee_vault.to_account_info(),
-            }
-        );
-        anchor_lang::system_program::transfer(cpi_context, protocol_fee)?;
-
-        let cpi_context = CpiContext::new(
-            ctx.accounts.system_program.to_account_info(),
-            anchor_lang::system_program::Transfer{
-                from: ctx.accounts.creator.to_account_info(),
-                to: referral.to_account_info(),
-            }
-        );
-        anchor_lang::system_program::transfer(cpi_context, referral_fee)?;
-
-        referral.total_earned = referral.total_earned.checked_add(referral_fee).ok_or(MathError::Overflow)?;
-        referral.trade_count = referral.trade_count.checked_add(1).ok_or(MathError::Overflow)?;
-    }
-    else
-    {
-        let cpi_context = CpiContext::new(
-            ctx.accounts.system_program.to_account_info(),
-            anchor_lang::system_program::Transfer{
-                from: ctx.accounts.creator.to_account_info(),
-                to: ctx.accounts.fee_vault.to_account_info(),
-            }
-        );
-        anchor_lang::system_program::transfer(cpi_context, remaining_fee)?;
-    }
+    // The creator is the trader here: their share stays with them, the referral and protocol shares are paid
+    pay_trade_fee(
+        FeeSource::Trader(&ctx.accounts.creator),
+        fee,
+        ctx.accounts.global.creator_share_bps,
+        ctx.accounts.bonding_curve.referral_share_bps,
+        ctx.accounts.creator.to_account_info(),
+        &ctx.accounts.fee_vault,
+        ctx.accounts.referral.as_mut(),
+        &ctx.accounts.system_program,
+        ctx.accounts.mint.key(),
+        ctx.accounts.creator.key(),
+        ctx.program_id,
+    )?;
 
     ctx.accounts.bonding_curve.virtual_sol = ctx.accounts.bonding_curve.virtual_sol.checked_add(sol_after_fee).ok_or(MathError::Overflow)?;
     ctx.accounts.bonding_curve.virtual_token = ctx.accounts.bonding_curve.virtual_token.checked_sub(tokens_out).ok_or(MathError::Overflow)?;
     ctx.accounts.bonding_curve.real_sol_reserves = ctx.accounts.bonding_curve.real_sol_reserves.checked_add(sol_after_fee).ok_or(MathError::Overflow)?;
     ctx.accounts.bonding_curve.real_token = ctx.accounts.bonding_curve.real_token.checked_sub(tokens_out).ok_or(MathError::Overflow)?;
+    ctx.accounts.bonding_curve.last_trade_time = Clock::get()?.unix_timestamp;
 
-    if ctx.accounts.bonding_curve.real_sol_reserves >= ctx.accounts.global.graduation_threshold
-    {
-      ctx.accounts.bonding_curve.completed = true;
-      emit!(CompleteEvent {
-        mint: ctx.accounts.mint.key(),
-        real_sol_reserves: ctx.accounts.bonding_curve.real_sol_reserves,
-      });
-    }
+    check_graduation(&ctx.accounts.global, &mut ctx.accounts.bonding_curve, ctx.accounts.mint.key())?;
 
     emit!(TradeEvent {
         mint: ctx.accounts.mint.key(),
@@ -297,6 +301,34 @@ pub struct CreateAndBuyToken<'info>
     #[account(mut)]
     pub referral: Option<Account<'info, Referral>>,
 
+    /// Required when the program has an opening window
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + WalletPurchase::INIT_SPACE,
+        seeds = [WALLET_PURCHASE_SEED, mint.key().as_ref(), creator.key().as_ref()],
+        bump
+    )]
+    pub wallet_purchase: Option<Account<'info, WalletPurchase>>,
+
+    /// Only needed with a creator allocation
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + CreatorVesting::INIT_SPACE,
+        seeds = [VESTING_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub vesting: Option<Box<Account<'info, CreatorVesting>>>,
+
+    #[account(
+        init,
+        payer = creator,
+        associated_token::mint = mint,
+        associated_token::authority = vesting
+    )]
+    pub vesting_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
     /// CHECK: created via CPI to token metadata program
     #[account(mut)]
     pub metadata: UncheckedAccount<'info>,
@@ -0,0 +1,135 @@
+use anchor_lang::prelude::*;
+use crate::events::*;
+use crate::constants::*;
+use crate::state::*;
+use crate::errors::*;
+use super::create_token_2022::*;
+use crate::instructions::trade::graduation::check_graduation;
+use crate::instructions::trade::curve::{quote_buy, track_wallet_purchase, pay_trade_fee, FeeSource};
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{TokenAccount, Token2022};
+
+/// `create_token_2022` followed by the creator's bundled buy, as `create_and_buy_token`
+/// does for a Metaplex launch.
+pub fn _create_and_buy_token_2022(ctx: Context<CreateAndBuyToken2022>, name: String, symbol: String, uri: String, sol_amount: u64, min_tokens_out: u64, referral_share_bps: Option<u16>, start_time: Option<i64>, creator_allocation_bps: Option<u16>, mutable_metadata: Option<bool>) -> Result<()>
+{
+    let wallet_purchase_bump = ctx.bumps.wallet_purchase;
+    _create_token_2022(
+        Context::new(ctx.program_id, &mut ctx.accounts.create, &[], ctx.bumps.create),
+        name, symbol, uri, referral_share_bps, start_time, creator_allocation_bps, mutable_metadata,
+    )?;
+
+    //BUY — the creator's anti-snipe buy is allowed before start_time
+    let create = &mut ctx.accounts.create;
+
+    require!(sol_amount > 0, TradeError::ZeroAmount);
+
+    // Same pricing as any curve buy, TokensSold clipping included
+    let (fee, sol_after_fee, tokens_out) = quote_buy(&create.global, &create.bonding_curve, sol_amount)?;
+
+    require!(tokens_out >= min_tokens_out, TradeError::SlippageExceeded);
+    require!(tokens_out <= create.bonding_curve.real_token, TradeError::NotEnoughTokens);
+
+    // Same per-wallet cap as any other buy: the bundled buy counts toward the creator's opening window
+    track_wallet_purchase(
+        &create.global,
+        &create.bonding_curve,
+        ctx.accounts.wallet_purchase.as_mut(),
+        wallet_purchase_bump,
+        create.creator.key(),
+        create.mint.key(),
+        tokens_out,
+    )?;
+
+    let cpi_context = CpiContext::new(
+        create.system_program.to_account_info(),
+        anchor_lang::system_program::Transfer{
+            from: create.creator.to_account_info(),
+            to: create.bonding_curve.to_account_info(),
+        }
+    );
+
+    anchor_lang::system_program::transfer(cpi_context, sol_after_fee)?;
+
+    let signer_seeds: &[&[u8]] = &[
+    BONDING_CURVE_SEED,
+    create.mint.to_account_info().key.as_ref(),
+    &[create.bonding_curve.bump],
+    ];
+    let binding = [signer_seeds];
+    let cpi_context = CpiContext::new_with_signer(
+        create.token_program.to_account_info(),
+        anchor_spl::token_interface::TransferChecked{
+            from:   create.token_account.to_account_info(),
+            mint: create.mint.to_account_info(),
+            to: ctx.accounts.creator_token_account.to_account_info(),
+            authority: create.bonding_curve.to_account_info(),
+        },
+        &binding
+    );
+
+    anchor_spl::token_interface::transfer_checked(cpi_context, tokens_out, create.mint.decimals)?;
+
+    // The creator is the trader here: their share stays with them, the referral and protocol shares are paid
+    pay_trade_fee(
+        FeeSource::Trader(&create.creator),
+        fee,
+        create.global.creator_share_bps,
+        create.bonding_curve.referral_share_bps,
+        create.creator.to_account_info(),
+        &create.fee_vault,
+        create.referral.as_mut(),
+        &create.system_program,
+        create.mint.key(),
+        create.creator.key(),
+        ctx.program_id,
+    )?;
+
+    create.bonding_curve.virtual_sol = create.bonding_curve.virtual_sol.checked_add(sol_after_fee).ok_or(MathError::Overflow)?;
+    create.bonding_curve.virtual_token = create.bonding_curve.virtual_token.checked_sub(tokens_out).ok_or(MathError::Overflow)?;
+    create.bonding_curve.real_sol_reserves = create.bonding_curve.real_sol_reserves.checked_add(sol_after_fee).ok_or(MathError::Overflow)?;
+    create.bonding_curve.real_token = create.bonding_curve.real_token.checked_sub(tokens_out).ok_or(MathError::Overflow)?;
+    create.bonding_curve.last_trade_time = Clock::get()?.unix_timestamp;
+
+    check_graduation(&create.global, &mut create.bonding_curve, create.mint.key())?;
+
+    emit!(TradeEvent {
+        mint: create.mint.key(),
+        trader: create.creator.key(),
+        is_buy: true,
+        sol_amount: sol_after_fee,
+        token_amount: tokens_out,
+        fee,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CreateAndBuyToken2022<'info>
+{
+    pub create: CreateToken2022<'info>,
+
+    #[account(
+        init,
+        payer = create.creator,
+        associated_token::mint = create.mint,
+        associated_token::authority = create.creator,
+        associated_token::token_program = token_program,
+    )]
+    pub creator_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Required when the program has an opening window
+    #[account(
+        init,
+        payer = create.creator,
+        space = 8 + WalletPurchase::INIT_SPACE,
+        seeds = [WALLET_PURCHASE_SEED, create.mint.key().as_ref(), create.creator.key().as_ref()],
+        bump
+    )]
+    pub wallet_purchase: Option<Account<'info, WalletPurchase>>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
@@ -3,6 +3,8 @@ use crate::constants::*;
 use crate::events::*;
 use crate::state::*;
 use crate::errors::*;
+use crate::utils::validation::{validate_name, normalize_symbol, validate_uri};
+use crate::instructions::trade::graduation::max_creator_allocation;
 use anchor_spl::token::{Mint, TokenAccount, Token};
 use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::metadata::{
@@ -12,22 +14,23 @@ use anchor_spl::metadata::{
     mpl_token_metadata::types::DataV2,
 };
 
-pub fn _create_token(ctx: Context<CreateToken>, name: String, symbol: String, uri: String) -> Result<()>
+pub fn _create_token(ctx: Context<CreateToken>, name: String, symbol: String, uri: String, referral_share_bps: Option<u16>, start_time: Option<i64>, creator_allocation_bps: Option<u16>, mutable_metadata: Option<bool>) -> Result<()>
 {
-    require!(ctx.accounts.global.status == ProgramStatus::Running, AdminError::ProgramPaused);
+    let (name, symbol, uri, launch) = check_launch(&ctx.accounts.global, &name, &symbol, &uri, referral_share_bps, start_time, creator_allocation_bps, mutable_metadata)?;
+    let LaunchTerms { referral_share_bps, start_time, creator_allocation, curve_supply, mutable_metadata } = launch;
+
+    let launch_referrer = pay_creation_fee(
+        &ctx.accounts.global,
+        &ctx.accounts.creator,
+        &ctx.accounts.fee_vault,
+        ctx.accounts.referral.as_mut(),
+        &ctx.accounts.system_program,
+        ctx.accounts.mint.key(),
+        ctx.program_id,
+    )?;
 
     let bc = &mut ctx.accounts.bonding_curve;
-    bc.mint = ctx.accounts.mint.key();
-    bc.creator = ctx.accounts.creator.key();
-    bc.virtual_sol = ctx.accounts.global.initial_virtual_sol_reserves;
-    bc.virtual_token = ctx.accounts.global.initial_virtual_token_reserves;
-    bc.real_token = ctx.accounts.global.initial_real_token_reserves;
-    bc.real_sol_reserves = 0;
-    bc.token_total_supply = ctx.accounts.global.token_total_supply;
-    bc.start_time = Clock::get()?.unix_timestamp;
-    bc.completed = false;
-    bc.migrated = false;
-    bc.bump = ctx.bumps.bonding_curve;
+    init_bonding_curve(bc, &ctx.accounts.global, ctx.accounts.mint.key(), ctx.accounts.creator.key(), &launch, launch_referrer, ctx.bumps.bonding_curve);
 
     let mint_key = ctx.accounts.mint.key();
     let seeds = &[
@@ -47,9 +50,36 @@ pub fn _create_token(ctx: Context<CreateToken>, name: String, symbol: String, ur
             },
             signer_seeds,
         ),
-        ctx.accounts.global.token_total_supply,
+        curve_supply,
     )?;
 
+    if creator_allocation > 0
+    {
+        let (Some(vesting), Some(vesting_token_account)) = (&mut ctx.accounts.vesting, &ctx.accounts.vesting_token_account)
+        else {
+            return err!(LaunchError::VestingAccountsRequired);
+        };
+        vesting.mint = ctx.accounts.mint.key();
+        vesting.total_amount = creator_allocation;
+        vesting.claimed_amount = 0;
+        vesting.cliff = ctx.accounts.global.vesting_cliff;
+        vesting.duration = ctx.accounts.global.vesting_duration;
+        vesting.bump = ctx.bumps.vesting.ok_or(LaunchError::VestingAccountsRequired)?;
+
+        anchor_spl::token::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                anchor_spl::token::MintTo {
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: vesting_token_account.to_account_info(),
+                    authority: ctx.accounts.bonding_curve.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            creator_allocation,
+        )?;
+    }
+
     // Revoke freeze authority
     anchor_spl::token::set_authority(
         CpiContext::new_with_signer(
@@ -91,21 +121,207 @@ pub fn _create_token(ctx: Context<CreateToken>, name: String, symbol: String, ur
             collection: None,
             uses: None,
         },
-        false, // is_mutable
+        mutable_metadata, // is_mutable
         true,  // update_authority_is_signer
         None,  // collection_details
     )?;
 
+    // Revoke mint authority: the whole supply is minted. Done after the Metaplex CPI, which needs it as signer
+    anchor_spl::token::set_authority(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            anchor_spl::token::SetAuthority {
+                account_or_mint: ctx.accounts.mint.to_account_info(),
+                current_authority: ctx.accounts.bonding_curve.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        anchor_spl::token::spl_token::instruction::AuthorityType::MintTokens,
+        None,
+    )?;
+    ctx.accounts.bonding_curve.supply_locked = true;
+
     emit!(CreateEvent{
         creator: ctx.accounts.creator.key(),
         mint: ctx.accounts.mint.key(),
         name: ev_name,
         symbol: ev_symbol,
         uri: ev_uri,
+        referral_share_bps,
+        start_time,
+        creator_allocation,
+    });
+
+    emit!(SupplyLocked {
+        mint: ctx.accounts.mint.key(),
+        total_supply: ctx.accounts.bonding_curve.token_total_supply,
     });
     Ok(())
 }
 
+/// Launch settings once checked against Global, what every launch path writes on the curve
+#[derive(Clone, Copy)]
+pub struct LaunchTerms
+{
+    pub referral_share_bps: u16,
+    pub start_time: i64,
+    pub creator_allocation: u64,
+    pub curve_supply: u64, // minted into the curve vault
+    pub mutable_metadata: bool,
+}
+
+/// Checks and defaults shared by every launch path, before any lamport moves.
+/// Returns the normalized name, symbol and URI with the curve terms.
+#[allow(clippy::too_many_arguments)]
+pub fn check_launch(
+    global: &Global,
+    name: &str,
+    symbol: &str,
+    uri: &str,
+    referral_share_bps: Option<u16>,
+    start_time: Option<i64>,
+    creator_allocation_bps: Option<u16>,
+    mutable_metadata: Option<bool>,
+) -> Result<(String, String, String, LaunchTerms)>
+{
+    require!(global.status == ProgramStatus::Running, AdminError::ProgramPaused);
+
+    let name = validate_name(name)?;
+    let symbol = normalize_symbol(symbol)?;
+    let uri = validate_uri(uri)?;
+
+    // Opt-in: the creator can fix the URI until graduation, then it is locked at migration
+    let mutable_metadata = mutable_metadata.unwrap_or(false);
+
+    let referral_share_bps = referral_share_bps.unwrap_or(global.referral_share_bps);
+    require!(referral_share_bps >= global.min_referral_share_bps && referral_share_bps <= global.max_referral_share_bps, LaunchError::InvalidReferralShare);
+
+    // Scheduled launch: trading opens at start_time, bounded by max_start_delay
+    let now = Clock::get()?.unix_timestamp;
+    let start_time = start_time.unwrap_or(now);
+    let latest_start = now.checked_add(global.max_start_delay).ok_or(MathError::Overflow)?;
+    require!(start_time >= now && start_time <= latest_start, LaunchError::InvalidStartTime);
+
+    // Creator allocation: carved out of the supply that would otherwise go to the pool at migration
+    let creator_allocation_bps = creator_allocation_bps.unwrap_or(0);
+    require!(creator_allocation_bps <= global.max_creator_allocation_bps, LaunchError::InvalidCreatorAllocation);
+    let creator_allocation = (global.token_total_supply as u128)
+        .checked_mul(creator_allocation_bps as u128)
+        .ok_or(MathError::Overflow)?
+        .checked_div(10_000)
+        .ok_or(MathError::DivisionByZero)?;
+    let creator_allocation = u64::try_from(creator_allocation).map_err(|_| MathError::CastOverflow)?;
+    let curve_supply = global.token_total_supply.checked_sub(creator_allocation).ok_or(MathError::Overflow)?;
+    require!(curve_supply >= global.initial_real_token_reserves, LaunchError::InvalidCreatorAllocation);
+    // the vault must still pair a sold-out curve's SOL at the curve price at migration
+    require!(creator_allocation <= max_creator_allocation(global)?, LaunchError::InvalidCreatorAllocation);
+
+    Ok((name, symbol, uri, LaunchTerms {
+        referral_share_bps,
+        start_time,
+        creator_allocation,
+        curve_supply,
+        mutable_metadata,
+    }))
+}
+
+/// Fresh curve at the Global initial reserves
+pub fn init_bonding_curve(bc: &mut BondingCurve, global: &Global, mint: Pubkey, creator: Pubkey, launch: &LaunchTerms, launch_referrer: Option<Pubkey>, bump: u8)
+{
+    bc.mint = mint;
+    bc.creator = creator;
+    bc.pending_creator = None;
+    bc.virtual_sol = global.initial_virtual_sol_reserves;
+    bc.virtual_token = global.initial_virtual_token_reserves;
+    bc.real_token = global.initial_real_token_reserves;
+    bc.real_sol_reserves = 0;
+    bc.token_total_supply = global.token_total_supply;
+    bc.start_time = launch.start_time;
+    bc.presale_end_time = 0;
+    bc.presale_merkle_root = [0; 32];
+    bc.referral_share_bps = launch.referral_share_bps;
+    bc.launch_referrer = launch_referrer;
+    bc.creator_allocation = launch.creator_allocation;
+    bc.metadata_mutable = launch.mutable_metadata;
+    bc.supply_locked = false;
+    bc.last_trade_time = 0;
+    bc.sunset = false;
+    bc.completed = false;
+    bc.completed_at = 0;
+    bc.migrated = false;
+    bc.bump = bump;
+}
+
+/// Creation fee into fee_vault; the referrer, if any, gets creation_referral_share_bps of it.
+/// Shared by every launch path. Returns the referrer, kept on the curve as launch_referrer.
+pub fn pay_creation_fee<'info>(
+    global: &Global,
+    creator: &Signer<'info>,
+    fee_vault: &SystemAccount<'info>,
+    referral: Option<&mut Account<'info, Referral>>,
+    system_program: &Program<'info, System>,
+    mint: Pubkey,
+    program_id: &Pubkey,
+) -> Result<Option<Pubkey>>
+{
+    let mut protocol_fee = global.creation_fee;
+    let mut launch_referrer = None;
+
+    if let Some(referral) = referral
+    {
+        // Validate referral PDA
+        let (expected_pda, _) = Pubkey::find_program_address(
+            &[REFERRAL_SEED, referral.referrer.as_ref()],
+            program_id,
+        );
+        require!(referral.key() == expected_pda, TradeError::InvalidReferral);
+        launch_referrer = Some(referral.referrer);
+
+        let referral_fee = (global.creation_fee as u128)
+            .checked_mul(global.creation_referral_share_bps as u128)
+            .ok_or(MathError::Overflow)?
+            .checked_div(10_000)
+            .ok_or(MathError::DivisionByZero)?;
+        let referral_fee = u64::try_from(referral_fee).map_err(|_| MathError::CastOverflow)?;
+
+        if referral_fee > 0
+        {
+            protocol_fee = protocol_fee.checked_sub(referral_fee).ok_or(MathError::Overflow)?;
+
+            let cpi_context = CpiContext::new(
+                system_program.to_account_info(),
+                anchor_lang::system_program::Transfer{
+                    from: creator.to_account_info(),
+                    to: referral.to_account_info(),
+                }
+            );
+            anchor_lang::system_program::transfer(cpi_context, referral_fee)?;
+
+            referral.total_earned = referral.total_earned.checked_add(referral_fee).ok_or(MathError::Overflow)?;
+
+            emit!(ReferralPaid {
+                referrer: referral.referrer,
+                mint,
+                trader: creator.key(),
+                amount: referral_fee,
+            });
+        }
+    }
+
+    if protocol_fee > 0
+    {
+        let cpi_context = CpiContext::new(
+            system_program.to_account_info(),
+            anchor_lang::system_program::Transfer{
+                from: creator.to_account_info(),
+                to: fee_vault.to_account_info(),
+            }
+        );
+        anchor_lang::system_program::transfer(cpi_context, protocol_fee)?;
+    }
+    Ok(launch_referrer)
+}
+
 
 #[derive(Accounts)]
 pub struct CreateToken<'info>
@@ -145,6 +361,35 @@ pub struct CreateToken<'info>
     )]
     pub token_account: Account<'info, TokenAccount>,
 
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED],
+        bump,
+    )]
+    pub fee_vault: SystemAccount<'info>,
+
+    /// Referrer of the launch, gets a share of the creation fee
+    #[account(mut)]
+    pub referral: Option<Account<'info, Referral>>,
+
+    /// Only needed with a creator allocation
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + CreatorVesting::INIT_SPACE,
+        seeds = [VESTING_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub vesting: Option<Box<Account<'info, CreatorVesting>>>,
+
+    #[account(
+        init,
+        payer = creator,
+        associated_token::mint = mint,
+        associated_token::authority = vesting
+    )]
+    pub vesting_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
     /// CHECK: created via CPI to token metadata program
     #[account(mut)]
     pub metadata: UncheckedAccount<'info>,
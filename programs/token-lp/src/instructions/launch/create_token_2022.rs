@@ -0,0 +1,265 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::events::*;
+use crate::state::*;
+use crate::errors::*;
+use super::create_token::{check_launch, init_bonding_curve, pay_creation_fee, LaunchTerms};
+use crate::instructions::creator::update_token_uri::lock_metadata;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{
+    Mint,
+    TokenAccount,
+    Token2022,
+    token_metadata_initialize,
+    TokenMetadataInitialize,
+    spl_token_metadata_interface::state::TokenMetadata,
+};
+
+/// Same launch as `create_token`, but the mint is a Token-2022 mint carrying its own
+/// metadata (MetadataPointer -> mint, TokenMetadata with the bonding curve PDA as update
+/// authority while mutable, revoked otherwise).
+/// No Metaplex account, so no metadata program to clone and less rent.
+pub fn _create_token_2022(ctx: Context<CreateToken2022>, name: String, symbol: String, uri: String, referral_share_bps: Option<u16>, start_time: Option<i64>, creator_allocation_bps: Option<u16>, mutable_metadata: Option<bool>) -> Result<()>
+{
+    let (name, symbol, uri, launch) = check_launch(&ctx.accounts.global, &name, &symbol, &uri, referral_share_bps, start_time, creator_allocation_bps, mutable_metadata)?;
+    let LaunchTerms { referral_share_bps, start_time, creator_allocation, curve_supply, mutable_metadata } = launch;
+
+    let launch_referrer = pay_creation_fee(
+        &ctx.accounts.global,
+        &ctx.accounts.creator,
+        &ctx.accounts.fee_vault,
+        ctx.accounts.referral.as_mut(),
+        &ctx.accounts.system_program,
+        ctx.accounts.mint.key(),
+        ctx.program_id,
+    )?;
+
+    let bc = &mut ctx.accounts.bonding_curve;
+    init_bonding_curve(bc, &ctx.accounts.global, ctx.accounts.mint.key(), ctx.accounts.creator.key(), &launch, launch_referrer, ctx.bumps.bonding_curve);
+
+    let mint_key = ctx.accounts.mint.key();
+    let seeds = &[
+        BONDING_CURVE_SEED,
+        mint_key.as_ref(),
+        &[bc.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    // The TokenMetadata extension is reallocated by the token program: top up the mint's rent first
+    let token_metadata = TokenMetadata {
+        name: name.clone(),
+        symbol: symbol.clone(),
+        uri: uri.clone(),
+        ..Default::default()
+    };
+    let mint_info = ctx.accounts.mint.to_account_info();
+    let new_len = mint_info.data_len().checked_add(token_metadata.tlv_size_of()?).ok_or(MathError::Overflow)?;
+    let rent_top_up = Rent::get()?.minimum_balance(new_len).saturating_sub(mint_info.lamports());
+
+    if rent_top_up > 0
+    {
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.creator.to_account_info(),
+                    to: mint_info,
+                },
+            ),
+            rent_top_up,
+        )?;
+    }
+
+    token_metadata_initialize(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TokenMetadataInitialize {
+                program_id: ctx.accounts.token_program.to_account_info(),
+                metadata: ctx.accounts.mint.to_account_info(),
+                update_authority: ctx.accounts.bonding_curve.to_account_info(),
+                mint_authority: ctx.accounts.bonding_curve.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        name.clone(),
+        symbol.clone(),
+        uri.clone(),
+    )?;
+
+    if !mutable_metadata
+    {
+        lock_metadata(
+            ctx.accounts.bonding_curve.to_account_info(),
+            ctx.accounts.mint.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            None,
+            None,
+            signer_seeds,
+        )?;
+    }
+
+    anchor_spl::token_interface::mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            anchor_spl::token_interface::MintTo {
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.token_account.to_account_info(),
+                authority: ctx.accounts.bonding_curve.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        curve_supply,
+    )?;
+
+    if creator_allocation > 0
+    {
+        let (Some(vesting), Some(vesting_token_account)) = (&mut ctx.accounts.vesting, &ctx.accounts.vesting_token_account)
+        else {
+            return err!(LaunchError::VestingAccountsRequired);
+        };
+        vesting.mint = ctx.accounts.mint.key();
+        vesting.total_amount = creator_allocation;
+        vesting.claimed_amount = 0;
+        vesting.cliff = ctx.accounts.global.vesting_cliff;
+        vesting.duration = ctx.accounts.global.vesting_duration;
+        vesting.bump = ctx.bumps.vesting.ok_or(LaunchError::VestingAccountsRequired)?;
+
+        anchor_spl::token_interface::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                anchor_spl::token_interface::MintTo {
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: vesting_token_account.to_account_info(),
+                    authority: ctx.accounts.bonding_curve.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            creator_allocation,
+        )?;
+    }
+
+    // Revoke freeze authority
+    anchor_spl::token_interface::set_authority(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            anchor_spl::token_interface::SetAuthority {
+                account_or_mint: ctx.accounts.mint.to_account_info(),
+                current_authority: ctx.accounts.bonding_curve.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        anchor_spl::token_interface::spl_token_2022::instruction::AuthorityType::FreezeAccount,
+        None,
+    )?;
+
+    // Revoke mint authority: the whole supply is minted
+    anchor_spl::token_interface::set_authority(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            anchor_spl::token_interface::SetAuthority {
+                account_or_mint: ctx.accounts.mint.to_account_info(),
+                current_authority: ctx.accounts.bonding_curve.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        anchor_spl::token_interface::spl_token_2022::instruction::AuthorityType::MintTokens,
+        None,
+    )?;
+    ctx.accounts.bonding_curve.supply_locked = true;
+
+    emit!(CreateEvent{
+        creator: ctx.accounts.creator.key(),
+        mint: ctx.accounts.mint.key(),
+        name,
+        symbol,
+        uri,
+        referral_share_bps,
+        start_time,
+        creator_allocation,
+    });
+
+    emit!(SupplyLocked {
+        mint: ctx.accounts.mint.key(),
+        total_supply: ctx.accounts.bonding_curve.token_total_supply,
+    });
+    Ok(())
+}
+
+
+#[derive(Accounts)]
+pub struct CreateToken2022<'info>
+{
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_SEED],
+        bump,
+    )]
+    pub global: Account<'info, Global>,
+
+    #[account(
+    init,
+    payer = creator,
+    mint::decimals = global.token_decimal,
+    mint::authority = bonding_curve,
+    mint::freeze_authority = bonding_curve,
+    mint::token_program = token_program,
+    extensions::metadata_pointer::authority = bonding_curve,
+    extensions::metadata_pointer::metadata_address = mint,
+    )]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + BondingCurve::INIT_SPACE,
+        seeds = [BONDING_CURVE_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub bonding_curve: Account<'info, BondingCurve>,
+
+    #[account(
+        init,
+        payer = creator,
+        associated_token::mint = mint,
+        associated_token::authority = bonding_curve,
+        associated_token::token_program = token_program,
+    )]
+    pub token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED],
+        bump,
+    )]
+    pub fee_vault: SystemAccount<'info>,
+
+    /// Referrer of the launch, gets a share of the creation fee
+    #[account(mut)]
+    pub referral: Option<Account<'info, Referral>>,
+
+    /// Only needed with a creator allocation
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + CreatorVesting::INIT_SPACE,
+        seeds = [VESTING_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub vesting: Option<Box<Account<'info, CreatorVesting>>>,
+
+    #[account(
+        init,
+        payer = creator,
+        associated_token::mint = mint,
+        associated_token::authority = vesting,
+        associated_token::token_program = token_program,
+    )]
+    pub vesting_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
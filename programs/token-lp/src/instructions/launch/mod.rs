@@ -1,5 +1,13 @@
 pub mod create_token;
+pub mod create_token_2022;
 pub mod create_and_buy;
+pub mod create_and_buy_2022;
+pub mod set_presale;
+pub mod cancel_launch;
 
 pub use create_token::*;
+pub use create_token_2022::*;
 pub use create_and_buy::*;
+pub use create_and_buy_2022::*;
+pub use set_presale::*;
+pub use cancel_launch::*;
@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::events::*;
+use crate::state::*;
+use crate::errors::*;
+
+pub fn _set_presale(ctx: Context<SetPresale>, merkle_root: [u8; 32], presale_end_time: i64) -> Result<()>
+{
+    let now = Clock::get()?.unix_timestamp;
+    let bc = &mut ctx.accounts.bonding_curve;
+
+    // Only before anyone could trade: scheduled launch not started, or no trade ever made
+    require!(now < bc.start_time || bc.last_trade_time == 0, LaunchError::PresaleLocked);
+
+    let latest_end = bc.start_time.checked_add(ctx.accounts.global.max_presale_duration).ok_or(MathError::Overflow)?;
+    require!(presale_end_time > now && presale_end_time > bc.start_time && presale_end_time <= latest_end, LaunchError::InvalidPresaleWindow);
+
+    bc.presale_merkle_root = merkle_root;
+    bc.presale_end_time = presale_end_time;
+
+    emit!(PresaleConfigured {
+        mint: bc.mint,
+        merkle_root,
+        presale_end_time,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetPresale<'info>
+{
+    pub creator: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_SEED],
+        bump,
+    )]
+    pub global: Account<'info, Global>,
+
+    #[account(
+        mut,
+        seeds = [BONDING_CURVE_SEED, bonding_curve.mint.as_ref()],
+        bump,
+        constraint = bonding_curve.creator == creator.key() @ LaunchError::NotCreator,
+        constraint = bonding_curve.completed == false @ TradeError::CurveCompleted,
+    )]
+    pub bonding_curve: Account<'info, BondingCurve>,
+}
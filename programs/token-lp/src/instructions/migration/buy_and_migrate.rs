@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::instructions::trade::curve::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{TokenAccount, TokenInterface};
+use super::migrate_to_raydium::*;
+
+/// Graduating buy that migrates in the same transaction, so there is no dead window
+/// after CompleteEvent. The buy must complete the curve, otherwise the whole
+/// transaction fails: plain buys go through `buy_token`, which stays as cheap as before.
+/// The buyer is the migration caller and earns the crank reward.
+pub fn _buy_and_migrate(ctx: Context<BuyAndMigrate>, sol_amount: u64, min_tokens_out: u64) -> Result<()>
+{
+    let accounts = &mut *ctx.accounts;
+    let migrate = &mut accounts.migrate;
+    require!(!migrate.bonding_curve.presale_active(Clock::get()?.unix_timestamp), TradeError::PresaleActive);
+
+    // the buy goes through the migration's curve and vault: one copy of each, read by both halves
+    curve_buy(
+        CurveTrade {
+            program_id: ctx.program_id,
+            trader: &migrate.caller,
+            global: &migrate.global,
+            mint: &migrate.mint,
+            bonding_curve: &mut migrate.bonding_curve,
+            trader_token_account: &accounts.buyer_token_account,
+            token_account: &migrate.token_account,
+            creator_account: &accounts.creator_account,
+            fee_vault: &migrate.fee_vault,
+            referral: accounts.referral.as_mut(),
+            wallet_purchase: accounts.wallet_purchase.as_mut(),
+            wallet_purchase_bump: ctx.bumps.wallet_purchase,
+            system_program: &migrate.system_program,
+            token_program: &migrate.mint_token_program,
+        },
+        sol_amount,
+        min_tokens_out,
+    )?;
+
+    migrate.token_account.reload()?;
+    require!(migrate.bonding_curve.completed, MigrationError::NotCompleted);
+
+    _migrate_to_raydium(Context::new(ctx.program_id, &mut ctx.accounts.migrate, &[], ctx.bumps.migrate))
+}
+
+#[derive(Accounts)]
+pub struct BuyAndMigrate<'info>
+{
+    /// The caller is the buyer
+    pub migrate: MigrateRaydium<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = migrate.caller,
+        associated_token::mint = migrate.mint,
+        associated_token::authority = migrate.caller,
+        associated_token::token_program = token_program,
+    )]
+    pub buyer_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: the token creator, receives creator_share_bps of fees
+    #[account(
+        mut,
+        constraint = creator_account.key() == migrate.bonding_curve.creator,
+    )]
+    pub creator_account: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub referral: Option<Account<'info, Referral>>,
+
+    /// Required during the opening window, optional afterwards
+    #[account(
+        init_if_needed,
+        payer = migrate.caller,
+        space = 8 + WalletPurchase::INIT_SPACE,
+        seeds = [WALLET_PURCHASE_SEED, migrate.mint.key().as_ref(), migrate.caller.key().as_ref()],
+        bump
+    )]
+    pub wallet_purchase: Option<Account<'info, WalletPurchase>>,
+
+    pub system_program: Program<'info, System>,
+    #[account(address = migrate.mint_token_program.key())]
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
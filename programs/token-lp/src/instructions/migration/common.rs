@@ -0,0 +1,194 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::events::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::instructions::creator::update_token_uri::lock_metadata;
+use crate::utils::math::sqrt_u128;
+use anchor_spl::token_interface::Mint;
+
+/// What a migration takes off the curve and what it deposits in the pool
+pub struct MigrationAmounts
+{
+    pub migration_fee: u64,   // crank reward included
+    pub crank_reward: u64,
+    pub pool_sol: u64,
+    pub pool_tokens: u64,
+    pub leftover_tokens: u64, // vault tokens beyond the curve price, settled per leftover_mode
+}
+
+/// Pre-checks shared by every migration venue, run before the venue CPI: curve completed,
+/// not migrated, sunset or re-opened for sells, venue selected in Global, authority-only while paused. Takes
+/// the migration fee off the curve (crank reward to the caller, the rest to the fee vault),
+/// prices the pool deposit with `price_continuous_amounts`: every remaining lamport goes to the pool.
+pub fn begin_migration<'info>(
+    global: &Account<'info, Global>,
+    bonding_curve: &Account<'info, BondingCurve>,
+    caller: &Signer<'info>,
+    fee_vault: &SystemAccount<'info>,
+    vault_amount: u64,
+    venue: MigrationVenue,
+) -> Result<MigrationAmounts>
+{
+    require!(global.status != ProgramStatus::Paused || caller.key() == global.authority, AdminError::ProgramPaused);
+    require!(bonding_curve.completed, MigrationError::NotCompleted);
+    require!(!bonding_curve.migrated, MigrationError::AlreadyMigrated);
+    require!(!bonding_curve.sunset, TradeError::CurveSunset);
+    // the escape hatch prices holder exits without the creator allocation, which vests only after migration
+    require!(!bonding_curve.sells_reopened, MigrationError::SellsReopened);
+    require!(global.migration_venue == venue, MigrationError::VenueMismatch);
+
+    // The crank reward is carved out of the migration fee: the fee alone is checked against the reserves,
+    // which must keep SOL for the pool
+    let migration_fee = global.migration_fee_for(bonding_curve.real_sol_reserves).ok_or(MathError::Overflow)?;
+    let crank_reward = global.migration_crank_reward.min(migration_fee);
+    let protocol_fee = migration_fee.checked_sub(crank_reward).ok_or(MathError::Overflow)?;
+    require!(migration_fee < bonding_curve.real_sol_reserves, MigrationError::ReservesBelowMigrationFee);
+
+    // SOL amount for the pool (actual reserves minus migration fee)
+    let sol_for_pool = bonding_curve.real_sol_reserves.checked_sub(migration_fee).ok_or(MathError::Overflow)?;
+
+    let (pool_sol, pool_tokens, leftover_tokens) = price_continuous_amounts(
+        bonding_curve,
+        sol_for_pool,
+        vault_amount,
+        global.migration_price_tolerance_bps,
+    )?;
+    let liquidity = sqrt_u128((pool_sol as u128).checked_mul(pool_tokens as u128).ok_or(MathError::Overflow)?);
+    require!(liquidity > CPMM_LOCKED_LIQUIDITY, MigrationError::PoolDepositTooSmall);
+
+    bonding_curve.sub_lamports(migration_fee)?;
+    fee_vault.add_lamports(protocol_fee)?;
+    caller.add_lamports(crank_reward)?;
+
+    Ok(MigrationAmounts {
+        migration_fee,
+        crank_reward,
+        pool_sol,
+        pool_tokens,
+        leftover_tokens,
+    })
+}
+
+/// Splits `sol_for_pool` and the vault so the pool opens at the curve's last price
+/// (virtual_sol / virtual_token). All the SOL goes in and the vault keeps leftover tokens;
+/// `max_creator_allocation` keeps the vault deep enough at launch, and a vault still short
+/// (migration fee lowered since) goes in whole, at a price the tolerance has to accept.
+/// Returns (pool_sol, pool_tokens, leftover_tokens).
+/// Fails if the rounded amounts miss the curve price by more than `tolerance_bps`.
+pub fn price_continuous_amounts(bonding_curve: &BondingCurve, sol_for_pool: u64, vault_amount: u64, tolerance_bps: u16) -> Result<(u64, u64, u64)>
+{
+    let curve_tokens = (sol_for_pool as u128)
+        .checked_mul(bonding_curve.virtual_token as u128)
+        .ok_or(MathError::Overflow)?
+        .checked_div(bonding_curve.virtual_sol as u128)
+        .ok_or(MathError::DivisionByZero)?;
+    let pool_sol = sol_for_pool;
+    let pool_tokens = u64::try_from(curve_tokens).map_err(|_| MathError::CastOverflow)?.min(vault_amount);
+    require!(pool_sol > 0 && pool_tokens > 0, MigrationError::PoolDepositTooSmall);
+
+    // |pool price - curve price| / curve price, cross-multiplied:
+    // |sol * virtual_token - tokens * virtual_sol| / (tokens * virtual_sol)
+    let pool_side = (pool_sol as u128).checked_mul(bonding_curve.virtual_token as u128).ok_or(MathError::Overflow)?;
+    let curve_side = (pool_tokens as u128).checked_mul(bonding_curve.virtual_sol as u128).ok_or(MathError::Overflow)?;
+    let deviation = pool_side.abs_diff(curve_side).checked_mul(10_000).ok_or(MathError::Overflow)?;
+    let max_deviation = curve_side.checked_mul(tolerance_bps as u128).ok_or(MathError::Overflow)?;
+    require!(deviation <= max_deviation, MigrationError::PriceOutOfTolerance);
+
+    let leftover = vault_amount.checked_sub(pool_tokens).ok_or(MathError::Overflow)?;
+    Ok((pool_sol, pool_tokens, leftover))
+}
+
+/// Burns the leftover vault tokens, or sends them to the fee_receiver's token account,
+/// per `Global.leftover_mode`. Returns true if burnt.
+#[allow(clippy::too_many_arguments)]
+pub fn settle_leftover_tokens<'info>(
+    global: &Global,
+    bonding_curve: AccountInfo<'info>,
+    mint: &InterfaceAccount<'info, Mint>,
+    vault: AccountInfo<'info>,
+    leftover_token_account: Option<AccountInfo<'info>>,
+    mint_token_program: AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+    leftover: u64,
+) -> Result<bool>
+{
+    if global.leftover_mode == LeftoverMode::Protocol
+    {
+        let Some(leftover_token_account) = leftover_token_account
+        else {
+            return err!(MigrationError::LeftoverAccountRequired);
+        };
+
+        if leftover > 0
+        {
+            anchor_spl::token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    mint_token_program,
+                    anchor_spl::token_interface::TransferChecked {
+                        from: vault,
+                        mint: mint.to_account_info(),
+                        to: leftover_token_account,
+                        authority: bonding_curve,
+                    },
+                    signer_seeds,
+                ),
+                leftover,
+                mint.decimals,
+            )?;
+        }
+        return Ok(false);
+    }
+
+    if leftover > 0
+    {
+        anchor_spl::token_interface::burn(
+            CpiContext::new_with_signer(
+                mint_token_program,
+                anchor_spl::token_interface::Burn {
+                    mint: mint.to_account_info(),
+                    from: vault,
+                    authority: bonding_curve,
+                },
+                signer_seeds,
+            ),
+            leftover,
+        )?;
+    }
+    Ok(true)
+}
+
+/// Shared by every migration venue once the pool exists: freezes metadata left mutable
+/// during the curve, flags the curve migrated (pool and liquidity recorded for finalize_migration) and emits the venue's MigrateEvent.
+pub fn end_migration<'info>(
+    bonding_curve: &mut Account<'info, BondingCurve>,
+    mint: AccountInfo<'info>,
+    mint_token_program: AccountInfo<'info>,
+    metadata: Option<AccountInfo<'info>>,
+    token_metadata_program: Option<AccountInfo<'info>>,
+    signer_seeds: &[&[&[u8]]],
+    event: MigrateEvent,
+) -> Result<()>
+{
+    if bonding_curve.metadata_mutable
+    {
+        lock_metadata(
+            bonding_curve.to_account_info(),
+            mint,
+            mint_token_program,
+            metadata,
+            token_metadata_program,
+            signer_seeds,
+        )?;
+        bonding_curve.metadata_mutable = false;
+    }
+
+    bonding_curve.migrated = true;
+    bonding_curve.pool = event.pool;
+    bonding_curve.migrated_at = Clock::get()?.unix_timestamp;
+    bonding_curve.pool_sol_amount = event.sol_amount;
+    bonding_curve.pool_token_amount = event.token_amount;
+
+    emit!(event);
+    Ok(())
+}
@@ -0,0 +1,165 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::events::*;
+use crate::state::*;
+use crate::errors::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+/// Permissionless once migrated: closes the empty vault ATA and the bonding curve,
+/// rent to the fee vault or the creator per config. If `keep_graduation_record` is on,
+/// a GraduationRecord is left behind, its rent carved out of the curve's. Its creator is final:
+/// without the curve, transfer_creator can't run anymore.
+/// The vesting (if any) gets the graduation time and creator it used to read on the curve.
+pub fn _finalize_migration(ctx: Context<FinalizeMigration>) -> Result<()>
+{
+    require!(ctx.accounts.token_account.amount == 0, MigrationError::VaultNotEmpty);
+
+    let rent_receiver = match ctx.accounts.global.finalize_rent_destination
+    {
+        RentDestination::FeeVault => ctx.accounts.fee_vault.to_account_info(),
+        RentDestination::Creator => ctx.accounts.creator_account.to_account_info(),
+    };
+
+    let mint_key = ctx.accounts.mint.key();
+    let seeds = &[
+        BONDING_CURVE_SEED,
+        mint_key.as_ref(),
+        &[ctx.accounts.bonding_curve.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    let vault_rent = ctx.accounts.token_account.to_account_info().lamports();
+    anchor_spl::token_interface::close_account(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            anchor_spl::token_interface::CloseAccount {
+                account: ctx.accounts.token_account.to_account_info(),
+                destination: rent_receiver.clone(),
+                authority: ctx.accounts.bonding_curve.to_account_info(),
+            },
+            signer_seeds,
+        ),
+    )?;
+
+    let bonding_curve = &ctx.accounts.bonding_curve;
+
+    if bonding_curve.creator_allocation > 0
+    {
+        let Some(vesting) = &mut ctx.accounts.vesting
+        else {
+            return err!(LaunchError::VestingAccountsRequired);
+        };
+        vesting.creator = bonding_curve.creator;
+        vesting.graduated_at = bonding_curve.completed_at;
+    }
+
+    let graduation_record = ctx.accounts.global.keep_graduation_record;
+    if graduation_record
+    {
+        let Some(record) = &mut ctx.accounts.graduation_record
+        else {
+            return err!(MigrationError::GraduationRecordRequired);
+        };
+        record.mint = mint_key;
+        record.creator = bonding_curve.creator;
+        record.pool = bonding_curve.pool;
+        record.referral_share_bps = bonding_curve.referral_share_bps;
+        record.migrated_at = bonding_curve.migrated_at;
+        record.sol_amount = bonding_curve.pool_sol_amount;
+        record.token_amount = bonding_curve.pool_token_amount;
+        record.bump = ctx.bumps.graduation_record.ok_or(MigrationError::GraduationRecordRequired)?;
+
+        // the caller fronted the record rent, paid back from the curve's
+        let record_rent = record.to_account_info().lamports();
+        bonding_curve.sub_lamports(record_rent)?;
+        ctx.accounts.caller.add_lamports(record_rent)?;
+    }
+    else
+    {
+        // would be created at the caller's expense and left unused
+        require!(ctx.accounts.graduation_record.is_none(), MigrationError::GraduationRecordNotKept);
+    }
+
+    let pool = bonding_curve.pool;
+    let curve_rent = bonding_curve.to_account_info().lamports();
+    ctx.accounts.bonding_curve.close(rent_receiver.clone())?;
+
+    emit!(MigrationFinalized {
+        mint: mint_key,
+        pool,
+        rent_receiver: rent_receiver.key(),
+        rent_amount: curve_rent.checked_add(vault_rent).ok_or(MathError::Overflow)?,
+        graduation_record,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct FinalizeMigration<'info>
+{
+    /// Anyone, fronts the record rent and gets it back
+    #[account(mut)]
+    pub caller: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_SEED],
+        bump,
+    )]
+    pub global: Box<Account<'info, Global>>,
+
+    #[account(
+        mint::token_program = token_program,
+    )]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [BONDING_CURVE_SEED, mint.key().as_ref()],
+        bump,
+        constraint = bonding_curve.migrated @ MigrationError::NotMigrated,
+    )]
+    pub bonding_curve: Account<'info, BondingCurve>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = bonding_curve,
+        associated_token::token_program = token_program,
+    )]
+    pub token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Only when Global.keep_graduation_record
+    #[account(
+        init,
+        payer = caller,
+        space = 8 + GraduationRecord::INIT_SPACE,
+        seeds = [GRADUATION_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub graduation_record: Option<Box<Account<'info, GraduationRecord>>>,
+
+    /// Only needed with a creator allocation
+    #[account(
+        mut,
+        seeds = [VESTING_SEED, mint.key().as_ref()],
+        bump = vesting.bump,
+    )]
+    pub vesting: Option<Box<Account<'info, CreatorVesting>>>,
+
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED],
+        bump,
+    )]
+    pub fee_vault: SystemAccount<'info>,
+
+    /// CHECK: the token creator, rent receiver in Creator mode
+    #[account(
+        mut,
+        constraint = creator_account.key() == bonding_curve.creator,
+    )]
+    pub creator_account: SystemAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,310 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::events::*;
+use crate::state::*;
+use crate::errors::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::Token;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, Token2022};
+use raydium_cp_swap::program::RaydiumCpSwap;
+use raydium_cp_swap::states::PoolState;
+use raydium_cp_swap::cpi;
+
+/// Permissionless: withdraws only the LP share the pool fees added on top of the locked
+/// liquidity, then splits both sides between the creator, the launch referrer and the protocol.
+/// The locked principal never leaves the lock.
+pub fn _harvest_lp_fees(ctx: Context<HarvestLpFees>) -> Result<()>
+{
+    // Pool reserves net of the fees cp-swap keeps for itself
+    let (reserve_0, reserve_1, lp_supply) = {
+        let pool = ctx.accounts.pool_state.load()?;
+        let reserve_0 = ctx.accounts.token_0_vault.amount
+            .checked_sub(pool.protocol_fees_token_0).ok_or(MathError::Overflow)?
+            .checked_sub(pool.fund_fees_token_0).ok_or(MathError::Overflow)?;
+        let reserve_1 = ctx.accounts.token_1_vault.amount
+            .checked_sub(pool.protocol_fees_token_1).ok_or(MathError::Overflow)?
+            .checked_sub(pool.fund_fees_token_1).ok_or(MathError::Overflow)?;
+        (reserve_0, reserve_1, pool.lp_supply)
+    };
+
+    let current_liquidity = LpLock::liquidity_of(reserve_0, reserve_1, ctx.accounts.lp_lock.lp_amount, lp_supply).ok_or(MathError::Overflow)?;
+    let lp_to_withdraw = ctx.accounts.lp_lock.harvestable_lp(current_liquidity).ok_or(MathError::Overflow)?;
+    require!(lp_to_withdraw > 0, MigrationError::NothingToHarvest);
+
+    let mint_key = ctx.accounts.lp_lock.mint;
+    let seeds = &[
+        LP_LOCK_SEED,
+        mint_key.as_ref(),
+        &[ctx.accounts.lp_lock.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    let before_0 = ctx.accounts.lock_token_0.amount;
+    let before_1 = ctx.accounts.lock_token_1.amount;
+
+    let cpi_accounts = cpi::accounts::Withdraw {
+        owner: ctx.accounts.lp_lock.to_account_info(),
+        authority: ctx.accounts.authority_raydium.to_account_info(),
+        pool_state: ctx.accounts.pool_state.to_account_info(),
+        owner_lp_token: ctx.accounts.lp_lock_token_account.to_account_info(),
+        token_0_account: ctx.accounts.lock_token_0.to_account_info(),
+        token_1_account: ctx.accounts.lock_token_1.to_account_info(),
+        token_0_vault: ctx.accounts.token_0_vault.to_account_info(),
+        token_1_vault: ctx.accounts.token_1_vault.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info(),
+        token_program_2022: ctx.accounts.token_program_2022.to_account_info(),
+        vault_0_mint: ctx.accounts.vault_0_mint.to_account_info(),
+        vault_1_mint: ctx.accounts.vault_1_mint.to_account_info(),
+        lp_mint: ctx.accounts.lp_mint.to_account_info(),
+        memo_program: ctx.accounts.memo_program.to_account_info(),
+    };
+    let cpi_context = CpiContext::new_with_signer(ctx.accounts.cp_swap_program.to_account_info(), cpi_accounts, signer_seeds);
+    cpi::withdraw(cpi_context, lp_to_withdraw, 0, 0)?;
+
+    ctx.accounts.lock_token_0.reload()?;
+    ctx.accounts.lock_token_1.reload()?;
+    let amount_0 = ctx.accounts.lock_token_0.amount.checked_sub(before_0).ok_or(MathError::Overflow)?;
+    let amount_1 = ctx.accounts.lock_token_1.amount.checked_sub(before_1).ok_or(MathError::Overflow)?;
+
+    ctx.accounts.lp_lock.lp_amount = ctx.accounts.lp_lock.lp_amount.checked_sub(lp_to_withdraw).ok_or(MathError::Overflow)?;
+
+    // Referrer share only if the curve had a launch referrer and the share is on, otherwise it stays with the protocol
+    let referral_share_bps = match ctx.accounts.lp_lock.referrer
+    {
+        Some(_) if ctx.accounts.global.lp_fee_referral_share_bps > 0 => {
+            require!(ctx.accounts.referrer_token_0.is_some() && ctx.accounts.referrer_token_1.is_some(), MigrationError::LpLockAccountsRequired);
+            ctx.accounts.global.lp_fee_referral_share_bps
+        }
+        _ => 0,
+    };
+    let creator_share_bps = ctx.accounts.global.lp_fee_creator_share_bps;
+
+    let sides = [
+        (
+            amount_0,
+            &ctx.accounts.lock_token_0,
+            &ctx.accounts.vault_0_mint,
+            &ctx.accounts.token_0_program,
+            &ctx.accounts.creator_token_0,
+            &ctx.accounts.protocol_token_0,
+            ctx.accounts.referrer_token_0.as_ref(),
+        ),
+        (
+            amount_1,
+            &ctx.accounts.lock_token_1,
+            &ctx.accounts.vault_1_mint,
+            &ctx.accounts.token_1_program,
+            &ctx.accounts.creator_token_1,
+            &ctx.accounts.protocol_token_1,
+            ctx.accounts.referrer_token_1.as_ref(),
+        ),
+    ];
+
+    for (amount, from, mint, token_program, creator_account, protocol_account, referrer_account) in sides
+    {
+        let creator_amount = share_of(amount, creator_share_bps)?;
+        let referral_amount = share_of(amount, referral_share_bps)?;
+        let protocol_amount = amount
+            .checked_sub(creator_amount).ok_or(MathError::Overflow)?
+            .checked_sub(referral_amount).ok_or(MathError::Overflow)?;
+
+        let mut payouts = vec![
+            (creator_account.to_account_info(), creator_amount),
+            (protocol_account.to_account_info(), protocol_amount),
+        ];
+        if let Some(referrer_account) = referrer_account
+        {
+            payouts.push((referrer_account.to_account_info(), referral_amount));
+        }
+
+        for (to, payout) in payouts
+        {
+            if payout == 0
+            {
+                continue;
+            }
+            anchor_spl::token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    token_program.to_account_info(),
+                    anchor_spl::token_interface::TransferChecked {
+                        from: from.to_account_info(),
+                        mint: mint.to_account_info(),
+                        to,
+                        authority: ctx.accounts.lp_lock.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                payout,
+                mint.decimals,
+            )?;
+        }
+    }
+
+    emit!(LpFeesHarvested {
+        mint: mint_key,
+        lp_withdrawn: lp_to_withdraw,
+        amount_0,
+        amount_1,
+    });
+    Ok(())
+}
+
+fn share_of(amount: u64, bps: u16) -> Result<u64>
+{
+    let share = (amount as u128)
+        .checked_mul(bps as u128)
+        .ok_or(MathError::Overflow)?
+        .checked_div(10_000)
+        .ok_or(MathError::DivisionByZero)?;
+    u64::try_from(share).map_err(|_| MathError::CastOverflow.into())
+}
+
+#[derive(Accounts)]
+pub struct HarvestLpFees<'info>
+{
+    /// Anyone, the fees only go to the recipients below
+    #[account(mut)]
+    pub caller: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_SEED],
+        bump,
+    )]
+    pub global: Box<Account<'info, Global>>,
+
+    #[account(
+        mut,
+        seeds = [LP_LOCK_SEED, lp_lock.mint.as_ref()],
+        bump = lp_lock.bump,
+    )]
+    pub lp_lock: Box<Account<'info, LpLock>>,
+
+    pub cp_swap_program: Program<'info, RaydiumCpSwap>,
+
+    /// CHECK: pool vault and lp mint authority
+    #[account(
+        seeds = [
+            raydium_cp_swap::AUTH_SEED.as_bytes(),
+        ],
+        seeds::program = cp_swap_program,
+        bump,
+    )]
+    pub authority_raydium: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        address = lp_lock.pool_state,
+    )]
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    #[account(
+        mut,
+        address = lp_lock.lp_mint,
+    )]
+    pub lp_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = lp_mint,
+        associated_token::authority = lp_lock,
+        associated_token::token_program = token_program,
+    )]
+    pub lp_lock_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        address = pool_state.load()?.token_0_vault,
+    )]
+    pub token_0_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        address = pool_state.load()?.token_1_vault,
+    )]
+    pub token_1_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        address = token_0_vault.mint,
+        mint::token_program = token_0_program,
+    )]
+    pub vault_0_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        address = token_1_vault.mint,
+        mint::token_program = token_1_program,
+    )]
+    pub vault_1_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Harvested token_0, paid out right away
+    #[account(
+        init_if_needed,
+        payer = caller,
+        associated_token::mint = vault_0_mint,
+        associated_token::authority = lp_lock,
+        associated_token::token_program = token_0_program,
+    )]
+    pub lock_token_0: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Harvested token_1, paid out right away
+    #[account(
+        init_if_needed,
+        payer = caller,
+        associated_token::mint = vault_1_mint,
+        associated_token::authority = lp_lock,
+        associated_token::token_program = token_1_program,
+    )]
+    pub lock_token_1: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = vault_0_mint,
+        constraint = creator_token_0.owner == lp_lock.creator,
+    )]
+    pub creator_token_0: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = vault_1_mint,
+        constraint = creator_token_1.owner == lp_lock.creator,
+    )]
+    pub creator_token_1: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = vault_0_mint,
+        constraint = protocol_token_0.owner == global.fee_receiver,
+    )]
+    pub protocol_token_0: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = vault_1_mint,
+        constraint = protocol_token_1.owner == global.fee_receiver,
+    )]
+    pub protocol_token_1: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Only when the curve had a launch referrer and lp_fee_referral_share_bps is set
+    #[account(
+        mut,
+        token::mint = vault_0_mint,
+        constraint = Some(referrer_token_0.owner) == lp_lock.referrer,
+    )]
+    pub referrer_token_0: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(
+        mut,
+        token::mint = vault_1_mint,
+        constraint = Some(referrer_token_1.owner) == lp_lock.referrer,
+    )]
+    pub referrer_token_1: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// CHECK: spl memo program, checked by cp-swap
+    pub memo_program: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub token_program_2022: Program<'info, Token2022>,
+    pub token_0_program: Interface<'info, TokenInterface>,
+    pub token_1_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
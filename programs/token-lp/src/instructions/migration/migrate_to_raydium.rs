@@ -4,43 +4,99 @@ use crate::state::*;
 use crate::errors::*;
 use anchor_spl::token::Token;
 use crate::events::*;
-use anchor_spl::associated_token::AssociatedToken;
+use super::common::{begin_migration, settle_leftover_tokens, end_migration};
+use anchor_spl::metadata::Metadata;
+use anchor_spl::associated_token::{AssociatedToken, get_associated_token_address_with_program_id};
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use raydium_cp_swap::program::RaydiumCpSwap;
 use raydium_cp_swap::states::AmmConfig;
 use raydium_cp_swap::states::{POOL_SEED, POOL_LP_MINT_SEED, POOL_VAULT_SEED, OBSERVATION_SEED};
 use raydium_cp_swap::cpi;
 
+/// Raydium CPMM venue. Permissionless once the curve is completed: the caller earns
+/// `migration_crank_reward` out of the migration fee. While the program is paused only the authority can migrate.
 pub fn _migrate_to_raydium(ctx: Context<MigrateRaydium>) -> Result<()>
 {
-    require!(ctx.accounts.global.status != ProgramStatus::Paused, AdminError::ProgramPaused);
-
-    //transfer MIGRATION_FEE
+    // Only the amounts matching the curve's last price go in the pool, so it opens at that price
+    let amounts = begin_migration(
+        &ctx.accounts.global,
+        &ctx.accounts.bonding_curve,
+        &ctx.accounts.caller,
+        &ctx.accounts.fee_vault,
+        ctx.accounts.token_account.amount,
+        MigrationVenue::RaydiumCpmm,
+    )?;
+    let sol_for_pool = amounts.pool_sol;
+    let token_amount = amounts.pool_tokens;
 
     let signer_seeds: &[&[u8]] = &[
         BONDING_CURVE_SEED,
         ctx.accounts.mint.to_account_info().key.as_ref(),
         &[ctx.accounts.bonding_curve.bump],
     ];
+    let signer = [signer_seeds];
 
-    ctx.accounts.bonding_curve.sub_lamports(MIGRATION_FEE)?;
-    ctx.accounts.fee_vault.add_lamports(MIGRATION_FEE)?;
-
-    // Calculate SOL amount for Raydium pool (actual reserves minus migration fee)
-    let sol_for_pool = ctx.accounts.bonding_curve.real_sol_reserves.checked_sub(MIGRATION_FEE).ok_or(MathError::Overflow)?;
+    // The caller creates the pool: cp-swap charges the pool accounts to its creator and the curve PDA,
+    // which carries data, can't pay them. The deposit goes through two transit accounts owned by the caller.
+
+    // Wrap: the curve's lamports go into the wSOL transit account
+    ctx.accounts.bonding_curve.sub_lamports(sol_for_pool)?;
+    ctx.accounts.wsol_account.add_lamports(sol_for_pool)?;
+    anchor_spl::token::sync_native(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            anchor_spl::token::SyncNative {
+                account: ctx.accounts.wsol_account.to_account_info(),
+            },
+        ),
+    )?;
+
+    anchor_spl::token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.mint_token_program.to_account_info(),
+            anchor_spl::token_interface::TransferChecked {
+                from: ctx.accounts.token_account.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.pool_token_account.to_account_info(),
+                authority: ctx.accounts.bonding_curve.to_account_info(),
+            },
+            &signer,
+        ),
+        token_amount,
+        ctx.accounts.mint.decimals,
+    )?;
+
+    let leftover_burned = settle_leftover_tokens(
+        &ctx.accounts.global,
+        ctx.accounts.bonding_curve.to_account_info(),
+        &ctx.accounts.mint,
+        ctx.accounts.token_account.to_account_info(),
+        ctx.accounts.leftover_token_account.as_ref().map(|a| a.to_account_info()),
+        ctx.accounts.mint_token_program.to_account_info(),
+        &signer,
+        amounts.leftover_tokens,
+    )?;
+
+    // cp-swap orders the pair by mint key: put the token and wSOL transit accounts on their side
+    let mint_is_token_0 = ctx.accounts.token_0_mint.key() == ctx.accounts.mint.key();
+    let (creator_token_0, creator_token_1, init_amount_0, init_amount_1) = if mint_is_token_0 {
+        (ctx.accounts.pool_token_account.to_account_info(), ctx.accounts.wsol_account.to_account_info(), token_amount, sol_for_pool)
+    } else {
+        (ctx.accounts.wsol_account.to_account_info(), ctx.accounts.pool_token_account.to_account_info(), sol_for_pool, token_amount)
+    };
 
     ////////init raydium
 
     let cpi_accounts = cpi::accounts::Initialize {
-        creator: ctx.accounts.bonding_curve.to_account_info(),
+        creator: ctx.accounts.caller.to_account_info(),
         amm_config: ctx.accounts.amm_config.to_account_info(),
         authority: ctx.accounts.authority_raydium.to_account_info(),
         pool_state: ctx.accounts.pool_state.to_account_info(),
         token_0_mint: ctx.accounts.token_0_mint.to_account_info(),
         token_1_mint: ctx.accounts.token_1_mint.to_account_info(),
         lp_mint: ctx.accounts.lp_mint.to_account_info(),
-        creator_token_0: ctx.accounts.creator_token_0.to_account_info(),
-        creator_token_1: ctx.accounts.creator_token_1.to_account_info(),
+        creator_token_0,
+        creator_token_1,
         creator_lp_token: ctx.accounts.creator_lp_token.to_account_info(),
         token_0_vault: ctx.accounts.token_0_vault.to_account_info(),
         token_1_vault: ctx.accounts.token_1_vault.to_account_info(),
@@ -53,35 +109,155 @@ pub fn _migrate_to_raydium(ctx: Context<MigrateRaydium>) -> Result<()>
         system_program: ctx.accounts.system_program.to_account_info(),
         rent: ctx.accounts.rent.to_account_info(),
     };
-    let signer = [signer_seeds];
-    let cpi_context = CpiContext::new_with_signer(ctx.accounts.cp_swap_program.to_account_info(), cpi_accounts, &signer);
-    cpi::initialize(cpi_context, sol_for_pool, ctx.accounts.token_account.amount, u64::try_from(Clock::get()?.unix_timestamp).map_err(|_| error!(MathError::CastOverflow))?)?;
-    //burn
-
-    let signer2 = [signer_seeds];
-    let cpi_context = CpiContext::new_with_signer(
-        ctx.accounts.token_program.to_account_info(),
-        anchor_spl::token_interface::Burn{
-            from: ctx.accounts.creator_lp_token.to_account_info(),
-            mint: ctx.accounts.lp_mint.to_account_info(),
-            authority: ctx.accounts.bonding_curve.to_account_info()
-        },
-        &signer2
-    );
+    let caller_lamports = ctx.accounts.caller.lamports();
+    let cpi_context = CpiContext::new(ctx.accounts.cp_swap_program.to_account_info(), cpi_accounts);
+    cpi::initialize(cpi_context, init_amount_0, init_amount_1, u64::try_from(Clock::get()?.unix_timestamp).map_err(|_| error!(MathError::CastOverflow))?)?;
+
+    // The fee vault pays back what cp-swap charged the caller: pool accounts rent and create_pool_fee
+    let pool_cost = caller_lamports.checked_sub(ctx.accounts.caller.lamports()).ok_or(MathError::Overflow)?;
+    let fee_vault_seeds: &[&[u8]] = &[FEE_VAULT_SEED, &[ctx.bumps.fee_vault]];
+    let fee_vault_signer = [fee_vault_seeds];
+    anchor_lang::system_program::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.fee_vault.to_account_info(),
+                to: ctx.accounts.caller.to_account_info(),
+            },
+            &fee_vault_signer,
+        ),
+        pool_cost,
+    )?;
+
+    // Both transit accounts are empty now: close them, rent back to the caller who funded them
+    for (account, token_program) in [
+        (ctx.accounts.wsol_account.to_account_info(), ctx.accounts.token_program.to_account_info()),
+        (ctx.accounts.pool_token_account.to_account_info(), ctx.accounts.mint_token_program.to_account_info()),
+    ]
+    {
+        anchor_spl::token_interface::close_account(
+            CpiContext::new(
+                token_program,
+                anchor_spl::token_interface::CloseAccount {
+                    account,
+                    destination: ctx.accounts.caller.to_account_info(),
+                    authority: ctx.accounts.caller.to_account_info(),
+                },
+            ),
+        )?;
+    }
 
     let lp_data = ctx.accounts.creator_lp_token.to_account_info();
     let lp_token = TokenAccount::try_deserialize(&mut&lp_data.data.borrow()[..])?;
-    anchor_spl::token_interface::burn(cpi_context, lp_token.amount)?;
-
-
-    ctx.accounts.bonding_curve.migrated = true;
-
-    emit!(MigrateEvent{
-        mint: ctx.accounts.mint.key(),
-        pool_state: ctx.accounts.pool_state.key()
-    });
-
 
+    if ctx.accounts.global.lp_mode == LpMode::Lock
+    {
+        //lock: the LP goes to the lock PDA, only its fees can be harvested
+
+        let (Some(lp_lock), Some(lp_lock_token_account)) = (&mut ctx.accounts.lp_lock, &ctx.accounts.lp_lock_token_account)
+        else {
+            return err!(MigrationError::LpLockAccountsRequired);
+        };
+        require!(lp_lock_token_account.key() == get_associated_token_address_with_program_id(&lp_lock.key(), &ctx.accounts.lp_mint.key(), &ctx.accounts.token_program.key()), MigrationError::LpLockAccountsRequired);
+
+        // The LP mint only exists since cpi::initialize: create the lock's LP ATA here
+        anchor_spl::associated_token::create(
+            CpiContext::new(
+                ctx.accounts.associated_token_program.to_account_info(),
+                anchor_spl::associated_token::Create {
+                    payer: ctx.accounts.caller.to_account_info(),
+                    associated_token: lp_lock_token_account.to_account_info(),
+                    authority: lp_lock.to_account_info(),
+                    mint: ctx.accounts.lp_mint.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                    token_program: ctx.accounts.token_program.to_account_info(),
+                },
+            ),
+        )?;
+
+        let lp_mint_data = ctx.accounts.lp_mint.to_account_info();
+        let lp_decimals = Mint::try_deserialize(&mut&lp_mint_data.data.borrow()[..])?.decimals;
+
+        anchor_spl::token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                anchor_spl::token_interface::TransferChecked {
+                    from: ctx.accounts.creator_lp_token.to_account_info(),
+                    mint: ctx.accounts.lp_mint.to_account_info(),
+                    to: lp_lock_token_account.to_account_info(),
+                    authority: ctx.accounts.caller.to_account_info(),
+                },
+            ),
+            lp_token.amount,
+            lp_decimals,
+        )?;
+
+        // cp-swap mints sqrt(amount_0 * amount_1) LP at init: at lock time one LP is one unit of sqrt(k)
+        lp_lock.mint = ctx.accounts.mint.key();
+        lp_lock.pool_state = ctx.accounts.pool_state.key();
+        lp_lock.lp_mint = ctx.accounts.lp_mint.key();
+        lp_lock.creator = ctx.accounts.bonding_curve.creator;
+        lp_lock.referrer = ctx.accounts.bonding_curve.launch_referrer;
+        lp_lock.lp_amount = lp_token.amount;
+        lp_lock.locked_liquidity = lp_token.amount as u128;
+        lp_lock.bump = ctx.bumps.lp_lock.ok_or(MigrationError::LpLockAccountsRequired)?;
+
+        emit!(LpLocked {
+            mint: lp_lock.mint,
+            pool_state: lp_lock.pool_state,
+            lp_amount: lp_lock.lp_amount,
+            locked_liquidity: lp_lock.locked_liquidity,
+        });
+    }
+    else
+    {
+        //burn
+
+        let cpi_context = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            anchor_spl::token_interface::Burn{
+                from: ctx.accounts.creator_lp_token.to_account_info(),
+                mint: ctx.accounts.lp_mint.to_account_info(),
+                authority: ctx.accounts.caller.to_account_info()
+            },
+        );
+        anchor_spl::token_interface::burn(cpi_context, lp_token.amount)?;
+    }
+
+    // The caller's LP account is empty too, its rent was paid back by the fee vault: it returns there
+    anchor_spl::token_interface::close_account(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            anchor_spl::token_interface::CloseAccount {
+                account: ctx.accounts.creator_lp_token.to_account_info(),
+                destination: ctx.accounts.fee_vault.to_account_info(),
+                authority: ctx.accounts.caller.to_account_info(),
+            },
+        ),
+    )?;
+
+    // Metadata left mutable during the curve is frozen once the token trades on Raydium
+    let mint_token_program = ctx.accounts.mint_token_program.to_account_info();
+    end_migration(
+        &mut ctx.accounts.bonding_curve,
+        ctx.accounts.mint.to_account_info(),
+        mint_token_program,
+        ctx.accounts.metadata.as_ref().map(|m| m.to_account_info()),
+        ctx.accounts.token_metadata_program.as_ref().map(|p| p.to_account_info()),
+        &signer,
+        MigrateEvent {
+            mint: ctx.accounts.mint.key(),
+            venue: MigrationVenue::RaydiumCpmm,
+            pool: ctx.accounts.pool_state.key(),
+            caller: ctx.accounts.caller.key(),
+            migration_fee: amounts.migration_fee,
+            crank_reward: amounts.crank_reward,
+            sol_amount: sol_for_pool,
+            token_amount,
+            leftover_tokens: amounts.leftover_tokens,
+            leftover_burned,
+        },
+    )?;
 
     Ok(())
 }
@@ -89,35 +265,59 @@ pub fn _migrate_to_raydium(ctx: Context<MigrateRaydium>) -> Result<()>
 #[derive(Accounts)]
 pub struct MigrateRaydium<'info>
 {
+    /// Anyone once the curve is completed, the authority only while paused
     #[account(mut)]
-    pub authority: Signer<'info>,
+    pub caller: Signer<'info>,
 
     #[account(
         seeds = [GLOBAL_SEED],
         bump,
-        has_one = authority,
     )]
     pub global: Account<'info, Global>,
 
-    #[account()]
+    /// mut: leftover tokens may be burnt
+    #[account(
+        mut,
+        mint::token_program = mint_token_program,
+    )]
     pub mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         mut,
         seeds = [BONDING_CURVE_SEED, mint.key().as_ref()],
         bump,
-        constraint = bonding_curve.completed == true,
-        constraint = bonding_curve.migrated == false,
     )]
     pub bonding_curve: Account<'info, BondingCurve>,
 
-        #[account(
+    /// Curve vault, ATA of the mint's own token program (SPL Token or Token-2022)
+    #[account(
         mut,
         associated_token::mint = mint,
         associated_token::authority = bonding_curve,
+        associated_token::token_program = mint_token_program,
     )]
     pub token_account: InterfaceAccount<'info, TokenAccount>,
 
+    /// Transit account of the pool tokens, created for the deposit and closed after it
+    #[account(
+        init,
+        payer = caller,
+        seeds = [MIGRATION_TOKEN_SEED, mint.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = caller,
+        token::token_program = mint_token_program,
+    )]
+    pub pool_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// fee_receiver's token account, only when leftover tokens are routed to the protocol
+    #[account(
+        mut,
+        token::mint = mint,
+        constraint = leftover_token_account.owner == global.fee_receiver,
+    )]
+    pub leftover_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
     #[account(
         mut,
         seeds = [FEE_VAULT_SEED],
@@ -180,23 +380,26 @@ pub struct MigrateRaydium<'info>
     )]
     pub lp_mint: UncheckedAccount<'info>,
 
-    /// payer token0 account
+    /// Native mint, the SOL side of the pool
     #[account(
-        mut,
-        token::mint = token_0_mint,
-        token::authority = bonding_curve,
+        address = anchor_spl::token::spl_token::native_mint::ID,
+        constraint = token_0_mint.key() == wsol_mint.key() || token_1_mint.key() == wsol_mint.key(),
     )]
-    pub creator_token_0: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub wsol_mint: Box<InterfaceAccount<'info, Mint>>,
 
-    /// creator token1 account
+    /// Transit wSOL account, funded from the curve's lamports and closed after the pool is created
     #[account(
-        mut,
-        token::mint = token_1_mint,
-        token::authority = bonding_curve,
+        init,
+        payer = caller,
+        seeds = [MIGRATION_WSOL_SEED, mint.key().as_ref()],
+        bump,
+        token::mint = wsol_mint,
+        token::authority = caller,
+        token::token_program = token_program,
     )]
-    pub creator_token_1: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub wsol_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
-    /// CHECK: creator lp ATA token account, init by cp-swap
+    /// CHECK: caller's LP ATA, init by cp-swap and closed once the LP is burnt or locked
     #[account(mut)]
     pub creator_lp_token: UncheckedAccount<'info>,
 
@@ -246,8 +449,30 @@ pub struct MigrateRaydium<'info>
     )]
     pub observation_state: UncheckedAccount<'info>,
 
+    /// CHECK: Metaplex metadata of a legacy mint, only needed if still mutable (checked by the metadata program)
+    #[account(mut)]
+    pub metadata: Option<UncheckedAccount<'info>>,
+
+    pub token_metadata_program: Option<Program<'info, Metadata>>,
+
+    /// Lock mode only: receives the LP instead of burning it
+    #[account(
+        init,
+        payer = caller,
+        space = 8 + LpLock::INIT_SPACE,
+        seeds = [LP_LOCK_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub lp_lock: Option<Box<Account<'info, LpLock>>>,
+
+    /// CHECK: LP ATA of the lock, created once cp-swap has created the LP mint (address checked in the handler)
+    #[account(mut)]
+    pub lp_lock_token_account: Option<UncheckedAccount<'info>>,
+
     /// Program to create mint account and mint tokens
     pub token_program: Program<'info, Token>,
+    /// Token program of the curve's mint, SPL Token or Token-2022
+    pub mint_token_program: Interface<'info, TokenInterface>,
     /// Spl token program or token program 2022
     pub token_0_program: Interface<'info, TokenInterface>,
     /// Spl token program or token program 2022
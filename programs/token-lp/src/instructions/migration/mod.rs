@@ -1,3 +1,11 @@
+pub mod common;
 pub mod migrate_to_raydium;
+pub mod harvest_lp_fees;
+pub mod finalize_migration;
+pub mod buy_and_migrate;
 
+pub use common::*;
 pub use migrate_to_raydium::*;
+pub use harvest_lp_fees::*;
+pub use finalize_migration::*;
+pub use buy_and_migrate::*;
@@ -3,9 +3,15 @@ pub mod launch;
 pub mod trade;
 pub mod migration;
 pub mod referral;
+pub mod vesting;
+pub mod creator;
+pub mod sunset;
 
 pub use admin::*;
 pub use launch::*;
 pub use trade::*;
 pub use referral::*;
 pub use migration::*;
+pub use vesting::*;
+pub use creator::*;
+pub use sunset::*;
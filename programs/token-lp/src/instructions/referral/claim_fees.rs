@@ -2,6 +2,7 @@ use anchor_lang::prelude::*;
 use crate::state::*;
 use crate::constants::*;
 use crate::errors::*;
+use crate::events::*;
 
 pub fn _claim_referral_fees(ctx: Context<ClaimReferralFees>) -> Result<()>
 {
@@ -12,6 +13,11 @@ pub fn _claim_referral_fees(ctx: Context<ClaimReferralFees>) -> Result<()>
 
     ctx.accounts.referral.sub_lamports(amount)?;
     ctx.accounts.user.add_lamports(amount)?;
+
+    emit!(ReferralClaimed {
+        referrer: ctx.accounts.user.key(),
+        amount,
+    });
     Ok(())
 }
 
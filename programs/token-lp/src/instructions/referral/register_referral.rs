@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
 use crate::state::*;
 use crate::constants::*;
+use crate::events::*;
 
 pub fn _register_referral(ctx: Context<RegisterReferral>) -> Result <()>
 {
@@ -8,6 +9,11 @@ pub fn _register_referral(ctx: Context<RegisterReferral>) -> Result <()>
     ctx.accounts.referral.total_earned = 0;
     ctx.accounts.referral.trade_count = 0;
     ctx.accounts.referral.bump = ctx.bumps.referral;
+
+    emit!(ReferralRegistered {
+        referrer: ctx.accounts.user.key(),
+        referral: ctx.accounts.referral.key(),
+    });
     Ok(())
 }
 
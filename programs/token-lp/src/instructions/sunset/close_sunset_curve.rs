@@ -0,0 +1,147 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::events::*;
+use crate::state::*;
+use crate::errors::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+/// Permissionless once every holder token is redeemed: burns what is left in the vault
+/// (and the unvested creator allocation), closes the accounts, rent back to the creator.
+pub fn _close_sunset_curve(ctx: Context<CloseSunsetCurve>) -> Result<()>
+{
+    let outstanding = ctx.accounts.bonding_curve.outstanding_tokens(ctx.accounts.mint.supply, ctx.accounts.token_account.amount).ok_or(MathError::Overflow)?;
+    require!(outstanding == 0, SunsetError::RedemptionsOutstanding);
+
+    let mint_key = ctx.accounts.mint.key();
+    let seeds = &[
+        BONDING_CURVE_SEED,
+        mint_key.as_ref(),
+        &[ctx.accounts.bonding_curve.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    anchor_spl::token_interface::burn(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            anchor_spl::token_interface::Burn {
+                mint: ctx.accounts.mint.to_account_info(),
+                from: ctx.accounts.token_account.to_account_info(),
+                authority: ctx.accounts.bonding_curve.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        ctx.accounts.token_account.amount,
+    )?;
+
+    anchor_spl::token_interface::close_account(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            anchor_spl::token_interface::CloseAccount {
+                account: ctx.accounts.token_account.to_account_info(),
+                destination: ctx.accounts.creator_account.to_account_info(),
+                authority: ctx.accounts.bonding_curve.to_account_info(),
+            },
+            signer_seeds,
+        ),
+    )?;
+
+    if ctx.accounts.bonding_curve.creator_allocation > 0
+    {
+        let (Some(vesting), Some(vesting_token_account)) = (&ctx.accounts.vesting, &ctx.accounts.vesting_token_account)
+        else {
+            return err!(LaunchError::VestingAccountsRequired);
+        };
+
+        let vesting_seeds = &[
+            VESTING_SEED,
+            mint_key.as_ref(),
+            &[vesting.bump],
+        ];
+        let vesting_signer_seeds = &[&vesting_seeds[..]];
+
+        anchor_spl::token_interface::burn(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                anchor_spl::token_interface::Burn {
+                    mint: ctx.accounts.mint.to_account_info(),
+                    from: vesting_token_account.to_account_info(),
+                    authority: vesting.to_account_info(),
+                },
+                vesting_signer_seeds,
+            ),
+            vesting_token_account.amount,
+        )?;
+
+        anchor_spl::token_interface::close_account(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                anchor_spl::token_interface::CloseAccount {
+                    account: vesting_token_account.to_account_info(),
+                    destination: ctx.accounts.creator_account.to_account_info(),
+                    authority: vesting.to_account_info(),
+                },
+                vesting_signer_seeds,
+            ),
+        )?;
+    }
+
+    emit!(SunsetCurveClosed {
+        mint: mint_key,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CloseSunsetCurve<'info>
+{
+    pub caller: Signer<'info>,
+
+    #[account(
+        mut,
+        mint::token_program = token_program,
+    )]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        close = creator_account,
+        seeds = [BONDING_CURVE_SEED, mint.key().as_ref()],
+        bump,
+        constraint = bonding_curve.sunset @ SunsetError::NotSunset,
+    )]
+    pub bonding_curve: Account<'info, BondingCurve>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = bonding_curve,
+        associated_token::token_program = token_program,
+    )]
+    pub token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Only needed with a creator allocation
+    #[account(
+        mut,
+        close = creator_account,
+        seeds = [VESTING_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub vesting: Option<Box<Account<'info, CreatorVesting>>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vesting,
+        associated_token::token_program = token_program,
+    )]
+    pub vesting_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// CHECK: the token creator, paid back the rent
+    #[account(
+        mut,
+        constraint = creator_account.key() == bonding_curve.creator,
+    )]
+    pub creator_account: SystemAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
@@ -0,0 +1,9 @@
+pub mod sunset_curve;
+pub mod redeem_sunset;
+pub mod close_sunset_curve;
+pub mod reopen_curve;
+
+pub use sunset_curve::*;
+pub use redeem_sunset::*;
+pub use close_sunset_curve::*;
+pub use reopen_curve::*;
@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::events::*;
+use crate::state::*;
+use crate::errors::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+/// Burn tokens of a sunset curve for their pro-rata share of real_sol_reserves, no fees.
+/// The share of the remaining holders is unchanged by earlier redemptions.
+pub fn _redeem_sunset(ctx: Context<RedeemSunset>, token_amount: u64) -> Result<()>
+{
+    require!(token_amount > 0, TradeError::ZeroAmount);
+
+    let outstanding = ctx.accounts.bonding_curve.outstanding_tokens(ctx.accounts.mint.supply, ctx.accounts.token_account.amount).ok_or(MathError::Overflow)?;
+    require!(outstanding > 0, SunsetError::NothingToRedeem);
+
+    let sol_amount = ctx.accounts.bonding_curve.redemption_amount(token_amount, outstanding).ok_or(MathError::Overflow)?;
+
+    anchor_spl::token_interface::burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            anchor_spl::token_interface::Burn {
+                mint: ctx.accounts.mint.to_account_info(),
+                from: ctx.accounts.holder_token_account.to_account_info(),
+                authority: ctx.accounts.holder.to_account_info(),
+            },
+        ),
+        token_amount,
+    )?;
+
+    ctx.accounts.bonding_curve.sub_lamports(sol_amount)?;
+    ctx.accounts.holder.add_lamports(sol_amount)?;
+
+    ctx.accounts.bonding_curve.real_sol_reserves = ctx.accounts.bonding_curve.real_sol_reserves.checked_sub(sol_amount).ok_or(MathError::Overflow)?;
+
+    emit!(SunsetRedeemed {
+        mint: ctx.accounts.mint.key(),
+        holder: ctx.accounts.holder.key(),
+        token_amount,
+        sol_amount,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RedeemSunset<'info>
+{
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    #[account(
+        mut,
+        mint::token_program = token_program,
+    )]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [BONDING_CURVE_SEED, mint.key().as_ref()],
+        bump,
+        constraint = bonding_curve.sunset @ SunsetError::NotSunset,
+    )]
+    pub bonding_curve: Account<'info, BondingCurve>,
+
+    #[account(
+        associated_token::mint = mint,
+        associated_token::authority = bonding_curve,
+        associated_token::token_program = token_program,
+    )]
+    pub token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = holder,
+        token::token_program = token_program,
+    )]
+    pub holder_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::events::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Authority escape hatch for a completed curve whose migration keeps failing: once
+/// `migration_timeout` has passed, sells reopen on the curve so holders can exit.
+/// Buys stay closed and the curve can no longer be migrated, only sunset later.
+pub fn _reopen_curve(ctx: Context<ReopenCurve>) -> Result<()>
+{
+    let bc = &mut ctx.accounts.bonding_curve;
+    require!(bc.completed, MigrationError::NotCompleted);
+    require!(!bc.sells_reopened, SunsetError::AlreadyReopened);
+    require!(ctx.accounts.global.migration_timeout > 0, SunsetError::SunsetDisabled);
+    require!(bc.migration_timed_out(ctx.accounts.global.migration_timeout, Clock::get()?.unix_timestamp), SunsetError::MigrationPending);
+
+    bc.sells_reopened = true;
+
+    emit!(CurveReopened {
+        mint: bc.mint,
+        completed_at: bc.completed_at,
+        real_sol_reserves: bc.real_sol_reserves,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ReopenCurve<'info>
+{
+    pub authority: Signer<'info>,
+
+    #[account(
+        has_one = authority,
+        seeds = [GLOBAL_SEED],
+        bump,
+    )]
+    pub global: Account<'info, Global>,
+
+    #[account(
+        mut,
+        seeds = [BONDING_CURVE_SEED, bonding_curve.mint.as_ref()],
+        bump = bonding_curve.bump,
+        constraint = bonding_curve.migrated == false @ MigrationError::AlreadyMigrated,
+        constraint = bonding_curve.sunset == false @ TradeError::CurveSunset,
+    )]
+    pub bonding_curve: Account<'info, BondingCurve>,
+}
@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::events::*;
+use crate::state::*;
+use crate::errors::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+/// Permissionless: anyone can sunset a curve that hasn't traded for `sunset_inactivity`,
+/// or a completed curve still not migrated after `migration_timeout`.
+/// Trading (and migration) stops for good and holders can redeem their tokens pro-rata.
+pub fn _sunset_curve(ctx: Context<SunsetCurve>) -> Result<()>
+{
+    let now = Clock::get()?.unix_timestamp;
+    let bc = &mut ctx.accounts.bonding_curve;
+
+    let reason = if bc.completed
+    {
+        require!(ctx.accounts.global.migration_timeout > 0, SunsetError::SunsetDisabled);
+        require!(bc.migration_timed_out(ctx.accounts.global.migration_timeout, now), SunsetError::MigrationPending);
+        SunsetReason::MigrationTimeout
+    } else {
+        require!(ctx.accounts.global.sunset_inactivity > 0, SunsetError::SunsetDisabled);
+        let inactive_since = bc.last_trade_time.max(bc.start_time);
+        let sunset_at = inactive_since.checked_add(ctx.accounts.global.sunset_inactivity).ok_or(MathError::Overflow)?;
+        require!(now >= sunset_at, SunsetError::CurveStillActive);
+        SunsetReason::Inactive
+    };
+
+    bc.sunset = true;
+
+    let outstanding_tokens = bc.outstanding_tokens(ctx.accounts.mint.supply, ctx.accounts.token_account.amount).ok_or(MathError::Overflow)?;
+
+    emit!(CurveSunset {
+        mint: bc.mint,
+        real_sol_reserves: bc.real_sol_reserves,
+        outstanding_tokens,
+        reason,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SunsetCurve<'info>
+{
+    pub caller: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_SEED],
+        bump,
+    )]
+    pub global: Account<'info, Global>,
+
+    #[account(
+        mint::token_program = token_program,
+    )]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [BONDING_CURVE_SEED, mint.key().as_ref()],
+        bump,
+        constraint = bonding_curve.migrated == false @ MigrationError::AlreadyMigrated,
+        constraint = bonding_curve.sunset == false @ TradeError::CurveSunset,
+    )]
+    pub bonding_curve: Account<'info, BondingCurve>,
+
+    #[account(
+        associated_token::mint = mint,
+        associated_token::authority = bonding_curve,
+        associated_token::token_program = token_program,
+    )]
+    pub token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
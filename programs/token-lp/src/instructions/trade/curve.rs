@@ -0,0 +1,365 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::events::*;
+use crate::state::*;
+use crate::errors::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::utils::math::{calculate_buy_amount, calculate_sell_amount, calculate_sol_for_tokens};
+use super::graduation::check_graduation;
+
+/// Accounts of a trade on the live curve, borrowed from Buy, Sell or Swap
+pub struct CurveTrade<'a, 'info>
+{
+    pub program_id: &'a Pubkey,
+    pub trader: &'a Signer<'info>,
+    pub global: &'a Global,
+    pub mint: &'a InterfaceAccount<'info, Mint>,
+    pub bonding_curve: &'a mut Account<'info, BondingCurve>,
+    pub trader_token_account: &'a InterfaceAccount<'info, TokenAccount>,
+    pub token_account: &'a InterfaceAccount<'info, TokenAccount>,
+    pub creator_account: &'a SystemAccount<'info>,
+    pub fee_vault: &'a SystemAccount<'info>,
+    pub referral: Option<&'a mut Account<'info, Referral>>,
+    pub wallet_purchase: Option<&'a mut Account<'info, WalletPurchase>>, // buys only
+    pub wallet_purchase_bump: Option<u8>,
+    pub system_program: &'a Program<'info, System>,
+    pub token_program: &'a Interface<'info, TokenInterface>,
+}
+
+/// Where the trade fee is paid from
+pub enum FeeSource<'a, 'info>
+{
+    Trader(&'a Signer<'info>),                      // system transfer, buys and Raydium swaps
+    Curve(&'a Account<'info, BondingCurve>),        // program-owned, curve sells
+}
+
+/// Curve buy shared by `buy_token`, `presale_buy` and `swap`
+pub fn curve_buy(mut trade: CurveTrade, sol_amount: u64, min_tokens_out: u64) -> Result<()>
+{
+    require!(sol_amount > 0, TradeError::ZeroAmount);
+    require!(trade.global.status != ProgramStatus::Paused, TradeError::ProgramPaused);
+    require!(trade.bonding_curve.completed == false, TradeError::CurveCompleted);
+    require!(!trade.bonding_curve.sunset, TradeError::CurveSunset);
+    require!(Clock::get()?.unix_timestamp >= trade.bonding_curve.start_time, TradeError::TradingNotStarted);
+
+    let (fee, sol_after_fee, tokens_out) = quote_buy(trade.global, trade.bonding_curve, sol_amount)?;
+
+    require!(tokens_out >= min_tokens_out, TradeError::SlippageExceeded);
+    require!(tokens_out <= trade.bonding_curve.real_token, TradeError::NotEnoughTokens);
+
+    track_wallet_purchase(
+        trade.global,
+        trade.bonding_curve,
+        trade.wallet_purchase.as_deref_mut(),
+        trade.wallet_purchase_bump,
+        trade.trader.key(),
+        trade.mint.key(),
+        tokens_out,
+    )?;
+
+    let cpi_context = CpiContext::new(
+        trade.system_program.to_account_info(),
+        anchor_lang::system_program::Transfer{
+            from: trade.trader.to_account_info(),
+            to: trade.bonding_curve.to_account_info(),
+        }
+    );
+
+    anchor_lang::system_program::transfer(cpi_context, sol_after_fee)?;
+
+
+    let signer_seeds: &[&[u8]] = &[
+    BONDING_CURVE_SEED,
+    trade.mint.to_account_info().key.as_ref(),
+    &[trade.bonding_curve.bump],
+    ];
+    let binding = [signer_seeds];
+    let cpi_context = CpiContext::new_with_signer(
+        trade.token_program.to_account_info(),
+        anchor_spl::token_interface::TransferChecked{
+            from:   trade.token_account.to_account_info(),
+            mint: trade.mint.to_account_info(),
+            to: trade.trader_token_account.to_account_info(),
+            authority: trade.bonding_curve.to_account_info(),
+        },
+        &binding
+    );
+
+    anchor_spl::token_interface::transfer_checked(cpi_context, tokens_out, trade.mint.decimals)?;
+
+    pay_trade_fee(
+        FeeSource::Trader(trade.trader),
+        fee,
+        trade.global.creator_share_bps,
+        trade.bonding_curve.referral_share_bps,
+        trade.creator_account.to_account_info(),
+        trade.fee_vault,
+        trade.referral.as_deref_mut(),
+        trade.system_program,
+        trade.mint.key(),
+        trade.trader.key(),
+        trade.program_id,
+    )?;
+
+    trade.bonding_curve.virtual_sol = trade.bonding_curve.virtual_sol.checked_add(sol_after_fee).ok_or(MathError::Overflow)?;
+    trade.bonding_curve.virtual_token = trade.bonding_curve.virtual_token.checked_sub(tokens_out).ok_or(MathError::Overflow)?;
+    trade.bonding_curve.real_sol_reserves = trade.bonding_curve.real_sol_reserves.checked_add(sol_after_fee).ok_or(MathError::Overflow)?;
+    trade.bonding_curve.real_token = trade.bonding_curve.real_token.checked_sub(tokens_out).ok_or(MathError::Overflow)?;
+    trade.bonding_curve.last_trade_time = Clock::get()?.unix_timestamp;
+
+    check_graduation(trade.global, trade.bonding_curve, trade.mint.key())?;
+
+    emit!(TradeEvent {
+        mint: trade.mint.key(),
+        trader: trade.trader.key(),
+        is_buy: true,
+        sol_amount: sol_after_fee,
+        token_amount: tokens_out,
+        fee,
+    });
+    Ok(())
+}
+
+/// Fee, SOL reaching the curve and tokens out for a buy of `sol_amount`.
+/// TokensSold graduation: the last buy takes what is left of real_token and pays only for that,
+/// never less than the curve price. Shared by curve buys and `create_and_buy_token`.
+pub fn quote_buy(global: &Global, bonding_curve: &BondingCurve, sol_amount: u64) -> Result<(u64, u64, u64)>
+{
+    let mut fee = fee_of(sol_amount, global.trade_fee_bps)?;
+    let mut sol_after_fee = sol_amount.checked_sub(fee).ok_or(MathError::Overflow)?;
+    let mut tokens_out = calculate_buy_amount(bonding_curve.virtual_sol, bonding_curve.virtual_token, sol_after_fee)?;
+
+    if global.graduation_mode == GraduationMode::TokensSold && tokens_out > bonding_curve.real_token
+    {
+        tokens_out = bonding_curve.real_token;
+        let sol_needed = calculate_sol_for_tokens(bonding_curve.virtual_sol, bonding_curve.virtual_token, tokens_out)?;
+        // gross amount whose fee leaves at least sol_needed on the curve
+        let fee_denominator = 10_000u128.checked_sub(global.trade_fee_bps as u128).ok_or(MathError::Overflow)?;
+        let gross = (sol_needed as u128)
+            .checked_mul(10_000)
+            .ok_or(MathError::Overflow)?
+            .checked_add(fee_denominator.checked_sub(1).ok_or(MathError::DivisionByZero)?)
+            .ok_or(MathError::Overflow)?
+            .checked_div(fee_denominator)
+            .ok_or(MathError::DivisionByZero)?;
+        // Rounding can push gross past the input: charge the input then, as long as it still pays the price
+        let gross = u64::try_from(gross).map_err(|_| MathError::CastOverflow)?.min(sol_amount);
+        fee = fee_of(gross, global.trade_fee_bps)?;
+        sol_after_fee = gross.checked_sub(fee).ok_or(MathError::Overflow)?;
+        require!(sol_after_fee >= sol_needed, TradeError::BelowCurvePrice);
+    }
+
+    Ok((fee, sol_after_fee, tokens_out))
+}
+
+/// Trade fee on `amount` at `fee_bps`
+pub(crate) fn fee_of(amount: u64, fee_bps: u16) -> Result<u64>
+{
+    let fee = (amount as u128)
+        .checked_mul(fee_bps as u128)
+        .ok_or(MathError::Overflow)?
+        .checked_div(10_000)
+        .ok_or(MathError::DivisionByZero)?;
+    u64::try_from(fee).map_err(|_| MathError::CastOverflow.into())
+}
+
+/// Opening window: cap each wallet's cumulative buys against snipers.
+/// Shared by curve buys and the creator's bundled buy in `create_and_buy_token`.
+pub fn track_wallet_purchase(
+    global: &Global,
+    bonding_curve: &BondingCurve,
+    wallet_purchase: Option<&mut Account<WalletPurchase>>,
+    wallet_purchase_bump: Option<u8>,
+    wallet: Pubkey,
+    mint: Pubkey,
+    tokens_out: u64,
+) -> Result<()>
+{
+    let opening_end = bonding_curve.start_time.checked_add(global.opening_window).ok_or(MathError::Overflow)?;
+    let in_opening_window = Clock::get()?.unix_timestamp < opening_end;
+    require!(!in_opening_window || wallet_purchase.is_some(), TradeError::WalletPurchaseRequired);
+
+    if let Some(wallet_purchase) = wallet_purchase
+    {
+        wallet_purchase.wallet = wallet;
+        wallet_purchase.mint = mint;
+        wallet_purchase.bump = wallet_purchase_bump.ok_or(TradeError::WalletPurchaseRequired)?;
+        wallet_purchase.tokens_bought = wallet_purchase.tokens_bought.checked_add(tokens_out).ok_or(MathError::Overflow)?;
+
+        if in_opening_window
+        {
+            let wallet_cap = (bonding_curve.token_total_supply as u128)
+                .checked_mul(global.max_wallet_buy_bps as u128)
+                .ok_or(MathError::Overflow)?
+                .checked_div(10_000)
+                .ok_or(MathError::DivisionByZero)?;
+            require!(wallet_purchase.tokens_bought as u128 <= wallet_cap, TradeError::WalletCapExceeded);
+        }
+    }
+    Ok(())
+}
+
+/// Curve sell shared by `sell_token` and `swap`
+pub fn curve_sell(mut trade: CurveTrade, token_amount: u64, min_sol_out: u64) -> Result<()>
+{
+    require!(token_amount > 0, TradeError::ZeroAmount);
+    require!(trade.global.status != ProgramStatus::Paused, TradeError::ProgramPaused);
+    require!(!trade.bonding_curve.completed || trade.bonding_curve.sells_reopened, TradeError::CurveCompleted);
+    require!(!trade.bonding_curve.sunset, TradeError::CurveSunset);
+    require!(Clock::get()?.unix_timestamp >= trade.bonding_curve.start_time, TradeError::TradingNotStarted);
+    let sol_out = calculate_sell_amount(trade.bonding_curve.virtual_sol, trade.bonding_curve.virtual_token, token_amount)?;
+
+    let fee = (sol_out as u128)
+        .checked_mul(trade.global.trade_fee_bps as u128)
+        .ok_or(MathError::Overflow)?
+        .checked_div(10_000)
+        .ok_or(MathError::DivisionByZero)?;
+    let fee = u64::try_from(fee).map_err(|_| MathError::CastOverflow)?;
+
+    let sol_after_fee = sol_out.checked_sub(fee).ok_or(MathError::Overflow)?;
+
+    require!(sol_after_fee >= min_sol_out, TradeError::SlippageExceeded);
+    require!(sol_out <= trade.bonding_curve.real_sol_reserves, TradeError::NotEnoughSol);
+
+    // Check bonding curve retains enough for rent exemption
+    let bc_lamports = trade.bonding_curve.to_account_info().lamports();
+    let rent = Rent::get()?.minimum_balance(8 + BondingCurve::INIT_SPACE);
+    require!(bc_lamports.checked_sub(sol_out).ok_or(MathError::Overflow)? >= rent, TradeError::InsufficientRentExemption);
+
+    //send tokens from seller to bonding curve
+    let cpi_context = CpiContext::new(
+        trade.token_program.to_account_info(),
+        anchor_spl::token_interface::TransferChecked{
+            authority: trade.trader.to_account_info(),
+            from: trade.trader_token_account.to_account_info(),
+            mint: trade.mint.to_account_info(),
+            to: trade.token_account.to_account_info(),
+        }
+    );
+
+    anchor_spl::token_interface::transfer_checked(cpi_context, token_amount, trade.mint.decimals)?;
+
+    //send sol from bonding curve to seller
+    trade.bonding_curve.sub_lamports(sol_after_fee)?;
+    trade.trader.add_lamports(sol_after_fee)?;
+
+    pay_trade_fee(
+        FeeSource::Curve(&*trade.bonding_curve),
+        fee,
+        trade.global.creator_share_bps,
+        trade.bonding_curve.referral_share_bps,
+        trade.creator_account.to_account_info(),
+        trade.fee_vault,
+        trade.referral.as_deref_mut(),
+        trade.system_program,
+        trade.mint.key(),
+        trade.trader.key(),
+        trade.program_id,
+    )?;
+
+    trade.bonding_curve.virtual_sol = trade.bonding_curve.virtual_sol.checked_sub(sol_out).ok_or(MathError::Overflow)?;
+    trade.bonding_curve.virtual_token = trade.bonding_curve.virtual_token.checked_add(token_amount).ok_or(MathError::Overflow)?;
+    trade.bonding_curve.real_sol_reserves = trade.bonding_curve.real_sol_reserves.checked_sub(sol_out).ok_or(MathError::Overflow)?;
+    trade.bonding_curve.real_token = trade.bonding_curve.real_token.checked_add(token_amount).ok_or(MathError::Overflow)?;
+    trade.bonding_curve.last_trade_time = Clock::get()?.unix_timestamp;
+
+    emit!(TradeEvent {
+        mint: trade.mint.key(),
+        trader: trade.trader.key(),
+        is_buy: false,
+        sol_amount: sol_after_fee,
+        token_amount: token_amount,
+        fee,
+    });
+    Ok(())
+}
+
+/// Splits `fee` between the creator (minus the referral share, carved out of it),
+/// the referrer if any and the protocol. Same skim on the curve, on Raydium and on the
+/// create_and_buy bundled buy, where the creator is the trader and keeps their share.
+#[allow(clippy::too_many_arguments)]
+pub fn pay_trade_fee<'info>(
+    source: FeeSource<'_, 'info>,
+    fee: u64,
+    creator_share_bps: u16,
+    referral_share_bps: u16,
+    creator_account: AccountInfo<'info>,
+    fee_vault: &SystemAccount<'info>,
+    referral: Option<&mut Account<'info, Referral>>,
+    system_program: &Program<'info, System>,
+    mint: Pubkey,
+    trader: Pubkey,
+    program_id: &Pubkey,
+) -> Result<()>
+{
+    // Creator fee — the referral share is carved out of it
+    let creator_fee = (fee as u128)
+        .checked_mul(creator_share_bps as u128)
+        .ok_or(MathError::Overflow)?
+        .checked_div(10_000)
+        .ok_or(MathError::DivisionByZero)?;
+    let mut creator_fee = u64::try_from(creator_fee).map_err(|_| MathError::CastOverflow)?;
+    let protocol_fee = fee.checked_sub(creator_fee).ok_or(MathError::Overflow)?;
+
+    if let Some(referral) = referral
+    {
+        // Validate referral PDA
+        let (expected_pda, _) = Pubkey::find_program_address(
+            &[REFERRAL_SEED, referral.referrer.as_ref()],
+            program_id,
+        );
+        require!(referral.key() == expected_pda, TradeError::InvalidReferral);
+
+        let referral_fee = (fee as u128)
+            .checked_mul(referral_share_bps as u128)
+            .ok_or(MathError::Overflow)?
+            .checked_div(10_000)
+            .ok_or(MathError::DivisionByZero)?;
+        // creator_share_bps may have been lowered since launch
+        let referral_fee = u64::try_from(referral_fee).map_err(|_| MathError::CastOverflow)?.min(creator_fee);
+
+        creator_fee = creator_fee.checked_sub(referral_fee).ok_or(MathError::Overflow)?;
+
+        move_lamports(&source, referral.to_account_info(), referral_fee, system_program)?;
+
+        referral.total_earned = referral.total_earned.checked_add(referral_fee).ok_or(MathError::Overflow)?;
+        referral.trade_count = referral.trade_count.checked_add(1).ok_or(MathError::Overflow)?;
+
+        emit!(ReferralPaid {
+            referrer: referral.referrer,
+            mint,
+            trader,
+            amount: referral_fee,
+        });
+    }
+
+    if creator_fee > 0 {
+        move_lamports(&source, creator_account, creator_fee, system_program)?;
+    }
+
+    move_lamports(&source, fee_vault.to_account_info(), protocol_fee, system_program)
+}
+
+fn move_lamports<'info>(source: &FeeSource<'_, 'info>, to: AccountInfo<'info>, amount: u64, system_program: &Program<'info, System>) -> Result<()>
+{
+    match source
+    {
+        // the creator buying their own token pays themselves nothing
+        FeeSource::Trader(trader) if to.key() == trader.key() => Ok(()),
+        FeeSource::Trader(trader) => {
+            let cpi_context = CpiContext::new(
+                system_program.to_account_info(),
+                anchor_lang::system_program::Transfer{
+                    from: trader.to_account_info(),
+                    to,
+                }
+            );
+            anchor_lang::system_program::transfer(cpi_context, amount)
+        }
+        FeeSource::Curve(bonding_curve) => {
+            bonding_curve.sub_lamports(amount)?;
+            to.add_lamports(amount)?;
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use anchor_lang::prelude::*;
+use crate::events::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::utils::math::sqrt_u128;
+
+/// Graduation metric of a curve and the threshold it is compared to, per `Global.graduation_mode`
+pub fn graduation_metric(global: &Global, bonding_curve: &BondingCurve) -> Result<(u64, u64)>
+{
+    match global.graduation_mode
+    {
+        GraduationMode::RealSol => Ok((bonding_curve.real_sol_reserves, global.graduation_threshold)),
+        GraduationMode::MarketCap => {
+            // curve price (virtual_sol / virtual_token) * token_total_supply
+            let market_cap = (bonding_curve.virtual_sol as u128)
+                .checked_mul(bonding_curve.token_total_supply as u128)
+                .ok_or(MathError::Overflow)?
+                .checked_div(bonding_curve.virtual_token as u128)
+                .ok_or(MathError::DivisionByZero)?;
+            let market_cap = u64::try_from(market_cap).map_err(|_| MathError::CastOverflow)?;
+            Ok((market_cap, global.graduation_market_cap))
+        }
+        GraduationMode::TokensSold => Ok((bonding_curve.real_token, 0)),
+    }
+}
+
+/// Curve reserves (real_sol, virtual_sol, virtual_token) once all of initial_real_token_reserves is sold
+pub fn exhausted_reserves(global: &Global) -> Result<(u64, u64, u64)>
+{
+    let k = (global.initial_virtual_sol_reserves as u128)
+        .checked_mul(global.initial_virtual_token_reserves as u128)
+        .ok_or(MathError::Overflow)?;
+    let virtual_token = global.initial_virtual_token_reserves
+        .checked_sub(global.initial_real_token_reserves)
+        .ok_or(MathError::Overflow)?;
+    let virtual_sol = k.checked_div(virtual_token as u128).ok_or(MathError::DivisionByZero)?;
+    let virtual_sol = u64::try_from(virtual_sol).map_err(|_| MathError::CastOverflow)?;
+    let real_sol = virtual_sol.checked_sub(global.initial_virtual_sol_reserves).ok_or(MathError::Overflow)?;
+    Ok((real_sol, virtual_sol, virtual_token))
+}
+
+/// Curve reserves (real_sol, virtual_sol, virtual_token) when `Global.graduation_mode` is first met,
+/// capped at the exhausted curve: the least SOL a completed curve can hold
+pub fn graduation_reserves(global: &Global) -> Result<(u64, u64, u64)>
+{
+    let (_, exhausted_sol, _) = exhausted_reserves(global)?;
+    let k = (global.initial_virtual_sol_reserves as u128)
+        .checked_mul(global.initial_virtual_token_reserves as u128)
+        .ok_or(MathError::Overflow)?;
+    let virtual_sol = match global.graduation_mode
+    {
+        GraduationMode::RealSol => (global.initial_virtual_sol_reserves as u128)
+            .checked_add(global.graduation_threshold as u128)
+            .ok_or(MathError::Overflow)?,
+        // market cap = virtual_sol * token_total_supply / virtual_token = virtual_sol² * token_total_supply / k
+        GraduationMode::MarketCap => sqrt_u128(
+            (global.graduation_market_cap as u128)
+                .checked_mul(k)
+                .ok_or(MathError::Overflow)?
+                .checked_div(global.token_total_supply as u128)
+                .ok_or(MathError::DivisionByZero)?
+        ),
+        GraduationMode::TokensSold => exhausted_sol as u128,
+    };
+    let virtual_sol = virtual_sol.clamp(global.initial_virtual_sol_reserves as u128, exhausted_sol as u128);
+    let virtual_token = k.checked_div(virtual_sol).ok_or(MathError::DivisionByZero)?;
+    let virtual_token = u64::try_from(virtual_token).map_err(|_| MathError::CastOverflow)?;
+    let virtual_sol = virtual_sol as u64; // clamped to exhausted_sol
+    let real_sol = virtual_sol.checked_sub(global.initial_virtual_sol_reserves).ok_or(MathError::Overflow)?;
+    Ok((real_sol, virtual_sol, virtual_token))
+}
+
+/// Largest creator allocation, in tokens, that still leaves a sold-out curve's vault enough tokens
+/// to pair its SOL, migration fee taken, at the curve's last price. A sold-out curve is the worst
+/// case: each lamport past graduation needs more pool tokens and leaves fewer in the vault.
+pub fn max_creator_allocation(global: &Global) -> Result<u64>
+{
+    let (real_sol, virtual_sol, virtual_token) = exhausted_reserves(global)?;
+    let migration_fee = global.migration_fee_for(real_sol).ok_or(MathError::Overflow)?;
+    let pool_tokens = (real_sol.saturating_sub(migration_fee) as u128)
+        .checked_mul(virtual_token as u128)
+        .ok_or(MathError::Overflow)?
+        .checked_div(virtual_sol as u128)
+        .ok_or(MathError::DivisionByZero)?;
+    // the vault of a sold-out curve holds token_total_supply - initial_real_token_reserves - allocation
+    let room = (global.token_total_supply as u128)
+        .saturating_sub(global.initial_real_token_reserves as u128)
+        .saturating_sub(pool_tokens);
+    u64::try_from(room).map_err(|_| MathError::CastOverflow.into())
+}
+
+/// Called after every curve buy (buy_token, presale_buy, create_and_buy):
+/// completes the curve and emits CompleteEvent once the configured metric is reached.
+pub fn check_graduation(global: &Global, bonding_curve: &mut BondingCurve, mint: Pubkey) -> Result<()>
+{
+    let (metric, threshold) = graduation_metric(global, bonding_curve)?;
+    let reached = match global.graduation_mode
+    {
+        GraduationMode::TokensSold => metric == 0,
+        _ => metric >= threshold,
+    };
+    if !reached
+    {
+        return Ok(());
+    }
+
+    bonding_curve.completed = true;
+    bonding_curve.completed_at = Clock::get()?.unix_timestamp;
+    emit!(CompleteEvent {
+        mint,
+        real_sol_reserves: bonding_curve.real_sol_reserves,
+        mode: global.graduation_mode,
+        metric,
+        threshold,
+    });
+    Ok(())
+}
@@ -1,5 +1,13 @@
 pub mod buy;
 pub mod sell;
+pub mod presale_buy;
+pub mod graduation;
+pub mod curve;
+pub mod swap;
 
 pub use buy::*;
 pub use sell::*;
+pub use presale_buy::*;
+pub use graduation::*;
+pub use curve::*;
+pub use swap::*;
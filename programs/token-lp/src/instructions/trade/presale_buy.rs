@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+use crate::errors::*;
+use crate::utils::merkle::{presale_leaf, verify_proof};
+use super::buy::*;
+
+/// Buy during the allowlisted presale. Each leaf is (wallet, max_tokens) and
+/// caps the wallet's cumulative presale buys, tracked in its WalletPurchase.
+pub fn _presale_buy(mut ctx: Context<Buy>, sol_amount: u64, min_tokens_out: u64, max_tokens: u64, proof: Vec<[u8; 32]>) -> Result<()>
+{
+    require!(ctx.accounts.bonding_curve.presale_active(Clock::get()?.unix_timestamp), TradeError::PresaleNotActive);
+    require!(ctx.accounts.wallet_purchase.is_some(), TradeError::WalletPurchaseRequired);
+
+    let leaf = presale_leaf(ctx.accounts.buyer.key().as_ref(), max_tokens);
+    require!(verify_proof(&proof, ctx.accounts.bonding_curve.presale_merkle_root, leaf), TradeError::InvalidPresaleProof);
+
+    buy_on_curve(&mut ctx, sol_amount, min_tokens_out)?;
+
+    let tokens_bought = ctx.accounts.wallet_purchase.as_ref().map(|wp| wp.tokens_bought).unwrap_or_default();
+    require!(tokens_bought <= max_tokens, TradeError::PresaleCapExceeded);
+    Ok(())
+}
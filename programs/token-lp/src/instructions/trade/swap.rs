@@ -0,0 +1,348 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::events::*;
+use crate::state::*;
+use crate::errors::*;
+use anchor_spl::token::Token;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use anchor_spl::associated_token::AssociatedToken;
+use raydium_cp_swap::program::RaydiumCpSwap;
+use raydium_cp_swap::states::{PoolState, POOL_SEED};
+use raydium_cp_swap::cpi;
+use super::curve::*;
+
+/// Single trade entry point: on the curve while it is live, on the Raydium pool once migrated
+/// (bonding_curve or, after finalize_migration, graduation_record; with neither, the canonical pool and no creator share),
+/// with the same fee skim and TradeEvent.
+/// `amount_in` is SOL on a buy and tokens on a sell, `min_amount_out` tokens on a buy and SOL after fee on a sell.
+pub fn _swap(ctx: Context<Swap>, is_buy: bool, amount_in: u64, min_amount_out: u64) -> Result<()>
+{
+    let live = ctx.accounts.bonding_curve.as_ref().is_some_and(|bonding_curve| !bonding_curve.migrated);
+    if live
+    {
+        swap_on_curve(ctx, is_buy, amount_in, min_amount_out)
+    } else {
+        swap_on_raydium(ctx, is_buy, amount_in, min_amount_out)
+    }
+}
+
+fn swap_on_curve(mut ctx: Context<Swap>, is_buy: bool, amount_in: u64, min_amount_out: u64) -> Result<()>
+{
+    // created by the account constraints and only closed by the Raydium leg: left behind, it would block the next one
+    require!(ctx.accounts.trader_wsol_account.is_none(), TradeError::UnexpectedWsolAccount);
+    let accounts = &mut ctx.accounts;
+    let (Some(bonding_curve), Some(token_account)) = (accounts.bonding_curve.as_deref_mut(), accounts.token_account.as_deref())
+    else {
+        return err!(TradeError::SwapAccountsRequired);
+    };
+    require!(accounts.creator_account.key() == bonding_curve.creator, TradeError::InvalidCreatorAccount);
+    if is_buy
+    {
+        require!(!bonding_curve.presale_active(Clock::get()?.unix_timestamp), TradeError::PresaleActive);
+    }
+
+    let trade = CurveTrade {
+        program_id: ctx.program_id,
+        trader: &accounts.trader,
+        global: &accounts.global,
+        mint: &accounts.mint,
+        bonding_curve,
+        trader_token_account: &accounts.trader_token_account,
+        token_account,
+        creator_account: &accounts.creator_account,
+        fee_vault: &accounts.fee_vault,
+        referral: accounts.referral.as_mut(),
+        wallet_purchase: if is_buy { accounts.wallet_purchase.as_mut() } else { None },
+        wallet_purchase_bump: ctx.bumps.wallet_purchase,
+        system_program: &accounts.system_program,
+        token_program: &accounts.token_program,
+    };
+
+    if is_buy
+    {
+        curve_buy(trade, amount_in, min_amount_out)
+    } else {
+        curve_sell(trade, amount_in, min_amount_out)
+    }
+}
+
+/// Raydium leg: a per-trader wSOL PDA created by this instruction is the transit account, closed (unwrapped)
+/// after the swap, so the trader's own wSOL ATA is never touched.
+/// Our fee is taken in SOL, before the swap on a buy and on the output on a sell.
+fn swap_on_raydium(mut ctx: Context<Swap>, is_buy: bool, amount_in: u64, min_amount_out: u64) -> Result<()>
+{
+    require!(amount_in > 0, TradeError::ZeroAmount);
+    require!(ctx.accounts.global.status != ProgramStatus::Paused, TradeError::ProgramPaused);
+
+    let accounts = &mut ctx.accounts;
+    let cp_swap_program = accounts.cp_swap_program.as_ref().ok_or(TradeError::SwapAccountsRequired)?;
+    let authority_raydium = accounts.authority_raydium.as_ref().ok_or(TradeError::SwapAccountsRequired)?;
+    let amm_config = accounts.amm_config.as_ref().ok_or(TradeError::SwapAccountsRequired)?;
+    let pool_state = accounts.pool_state.as_ref().ok_or(TradeError::SwapAccountsRequired)?;
+    let token_vault = accounts.token_vault.as_ref().ok_or(TradeError::SwapAccountsRequired)?;
+    let wsol_vault = accounts.wsol_vault.as_ref().ok_or(TradeError::SwapAccountsRequired)?;
+    let observation_state = accounts.observation_state.as_ref().ok_or(TradeError::SwapAccountsRequired)?;
+    let wsol_mint = accounts.wsol_mint.as_ref().ok_or(TradeError::SwapAccountsRequired)?;
+    let wsol_token_program = accounts.wsol_token_program.as_ref().ok_or(TradeError::SwapAccountsRequired)?;
+    let trader_wsol_account = accounts.trader_wsol_account.as_mut().ok_or(TradeError::SwapAccountsRequired)?;
+
+    // Finalized without a kept GraduationRecord, nothing remembers the pool or the creator:
+    // the mint trades on amm_config's canonical mint/wSOL pool and the creator share goes to the protocol
+    let (creator, creator_share_bps, referral_share_bps, pool) = match (&accounts.bonding_curve, &accounts.graduation_record)
+    {
+        (Some(bonding_curve), _) => (Some(bonding_curve.creator), accounts.global.creator_share_bps, bonding_curve.referral_share_bps, bonding_curve.pool),
+        (None, Some(record)) => (Some(record.creator), accounts.global.creator_share_bps, record.referral_share_bps, record.pool),
+        (None, None) => (None, 0, 0, canonical_pool(&cp_swap_program.key(), &amm_config.key(), &accounts.mint.key(), &wsol_mint.key())),
+    };
+    if let Some(creator) = creator
+    {
+        require!(accounts.creator_account.key() == creator, TradeError::InvalidCreatorAccount);
+    }
+    require!(pool_state.key() == pool, TradeError::InvalidPool);
+
+    let (input_token_account, output_token_account, input_vault, output_vault, input_token_program, output_token_program, input_token_mint, output_token_mint) = if is_buy {
+        (
+            trader_wsol_account.to_account_info(), accounts.trader_token_account.to_account_info(),
+            wsol_vault.to_account_info(), token_vault.to_account_info(),
+            wsol_token_program.to_account_info(), accounts.token_program.to_account_info(),
+            wsol_mint.to_account_info(), accounts.mint.to_account_info(),
+        )
+    } else {
+        (
+            accounts.trader_token_account.to_account_info(), trader_wsol_account.to_account_info(),
+            token_vault.to_account_info(), wsol_vault.to_account_info(),
+            accounts.token_program.to_account_info(), wsol_token_program.to_account_info(),
+            accounts.mint.to_account_info(), wsol_mint.to_account_info(),
+        )
+    };
+
+    let (fee, swap_amount_in) = if is_buy {
+        let fee = fee_of(amount_in, accounts.global.trade_fee_bps)?;
+        let sol_in = amount_in.checked_sub(fee).ok_or(MathError::Overflow)?;
+
+        // wrap the SOL going into the pool
+        let cpi_context = CpiContext::new(
+            accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer{
+                from: accounts.trader.to_account_info(),
+                to: trader_wsol_account.to_account_info(),
+            }
+        );
+        anchor_lang::system_program::transfer(cpi_context, sol_in)?;
+        anchor_spl::token::sync_native(
+            CpiContext::new(
+                wsol_token_program.to_account_info(),
+                anchor_spl::token::SyncNative {
+                    account: trader_wsol_account.to_account_info(),
+                },
+            ),
+        )?;
+        (fee, sol_in)
+    } else {
+        (0, amount_in)
+    };
+
+    let tokens_before = accounts.trader_token_account.amount;
+    let wsol_before = trader_wsol_account.amount;
+
+    let cpi_accounts = cpi::accounts::Swap {
+        payer: accounts.trader.to_account_info(),
+        authority: authority_raydium.to_account_info(),
+        amm_config: amm_config.to_account_info(),
+        pool_state: pool_state.to_account_info(),
+        input_token_account,
+        output_token_account,
+        input_vault,
+        output_vault,
+        input_token_program,
+        output_token_program,
+        input_token_mint,
+        output_token_mint,
+        observation_state: observation_state.to_account_info(),
+    };
+    let cpi_context = CpiContext::new(cp_swap_program.to_account_info(), cpi_accounts);
+    cpi::swap_base_input(cpi_context, swap_amount_in, min_amount_out)?;
+
+    accounts.trader_token_account.reload()?;
+    trader_wsol_account.reload()?;
+
+    let (fee, sol_amount, token_amount) = if is_buy {
+        let tokens_out = accounts.trader_token_account.amount.checked_sub(tokens_before).ok_or(MathError::Overflow)?;
+        (fee, swap_amount_in, tokens_out)
+    } else {
+        let sol_out = trader_wsol_account.amount.checked_sub(wsol_before).ok_or(MathError::Overflow)?;
+        let fee = fee_of(sol_out, accounts.global.trade_fee_bps)?;
+        let sol_after_fee = sol_out.checked_sub(fee).ok_or(MathError::Overflow)?;
+        require!(sol_after_fee >= min_amount_out, TradeError::SlippageExceeded);
+        (fee, sol_after_fee, amount_in)
+    };
+
+    // unwrap: the trader gets back the wSOL (sell proceeds) and the transit account rent
+    anchor_spl::token::close_account(
+        CpiContext::new(
+            wsol_token_program.to_account_info(),
+            anchor_spl::token::CloseAccount {
+                account: trader_wsol_account.to_account_info(),
+                destination: accounts.trader.to_account_info(),
+                authority: accounts.trader.to_account_info(),
+            },
+        ),
+    )?;
+
+    pay_trade_fee(
+        FeeSource::Trader(&accounts.trader),
+        fee,
+        creator_share_bps,
+        referral_share_bps,
+        accounts.creator_account.to_account_info(),
+        &accounts.fee_vault,
+        if creator.is_some() { accounts.referral.as_mut() } else { None },
+        &accounts.system_program,
+        accounts.mint.key(),
+        accounts.trader.key(),
+        ctx.program_id,
+    )?;
+
+    emit!(TradeEvent {
+        mint: accounts.mint.key(),
+        trader: accounts.trader.key(),
+        is_buy,
+        sol_amount,
+        token_amount,
+        fee,
+    });
+    Ok(())
+}
+
+/// cp-swap pool PDA of `amm_config` for the mint and wSOL, mints in cp-swap's order
+fn canonical_pool(cp_swap_program: &Pubkey, amm_config: &Pubkey, mint: &Pubkey, wsol_mint: &Pubkey) -> Pubkey
+{
+    let (token_0_mint, token_1_mint) = if mint < wsol_mint { (mint, wsol_mint) } else { (wsol_mint, mint) };
+    Pubkey::find_program_address(
+        &[POOL_SEED.as_bytes(), amm_config.as_ref(), token_0_mint.as_ref(), token_1_mint.as_ref()],
+        cp_swap_program,
+    ).0
+}
+
+#[derive(Accounts)]
+pub struct Swap<'info>
+{
+    #[account(mut)]
+    pub trader: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_SEED],
+        bump
+    )]
+    pub global: Box<Account<'info, Global>>,
+
+    #[account(
+        mint::token_program = token_program,
+    )]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// None once finalize_migration closed it, pass graduation_record instead
+    #[account(
+        mut,
+        seeds = [BONDING_CURVE_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub bonding_curve: Option<Box<Account<'info, BondingCurve>>>,
+
+    #[account(
+        seeds = [GRADUATION_SEED, mint.key().as_ref()],
+        bump = graduation_record.bump,
+    )]
+    pub graduation_record: Option<Box<Account<'info, GraduationRecord>>>,
+
+    #[account(
+        init_if_needed,
+        payer = trader,
+        associated_token::mint = mint,
+        associated_token::authority = trader,
+        associated_token::token_program = token_program,
+    )]
+    pub trader_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Curve vault, live curve only
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = bonding_curve,
+        associated_token::token_program = token_program,
+    )]
+    pub token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// CHECK: the token creator, receives creator_share_bps of fees (checked against the curve or the record, unused with neither)
+    #[account(mut)]
+    pub creator_account: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED],
+        bump,
+    )]
+    pub fee_vault: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub referral: Option<Account<'info, Referral>>,
+
+    /// Live curve buys, required during the opening window
+    #[account(
+        init_if_needed,
+        payer = trader,
+        space = 8 + WalletPurchase::INIT_SPACE,
+        seeds = [WALLET_PURCHASE_SEED, mint.key().as_ref(), trader.key().as_ref()],
+        bump
+    )]
+    pub wallet_purchase: Option<Account<'info, WalletPurchase>>,
+
+    // Raydium accounts, migrated curve only. Checked by cp-swap against pool_state,
+    // which must be the curve's pool.
+
+    pub cp_swap_program: Option<Program<'info, RaydiumCpSwap>>,
+
+    /// CHECK: pool vault authority, checked by cp-swap
+    pub authority_raydium: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: pool config, checked by cp-swap
+    pub amm_config: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    pub pool_state: Option<AccountLoader<'info, PoolState>>,
+
+    /// CHECK: pool vault of the mint, checked by cp-swap
+    #[account(mut)]
+    pub token_vault: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: pool vault of wSOL, checked by cp-swap
+    #[account(mut)]
+    pub wsol_vault: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: pool oracle, checked by cp-swap
+    #[account(mut)]
+    pub observation_state: Option<UncheckedAccount<'info>>,
+
+    #[account(
+        address = anchor_spl::token::spl_token::native_mint::ID,
+    )]
+    pub wsol_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    /// Transit wSOL account, created for the swap and closed after it
+    #[account(
+        init,
+        payer = trader,
+        seeds = [SWAP_WSOL_SEED, trader.key().as_ref()],
+        bump,
+        token::mint = wsol_mint,
+        token::authority = trader,
+        token::token_program = wsol_token_program,
+    )]
+    pub trader_wsol_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    pub wsol_token_program: Option<Program<'info, Token>>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
@@ -0,0 +1,111 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::events::*;
+use crate::state::*;
+use crate::errors::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use anchor_spl::associated_token::AssociatedToken;
+
+pub fn _claim_vested(ctx: Context<ClaimVested>) -> Result<()>
+{
+    // once finalize_migration closed the curve, the vesting holds what we read on it
+    let (creator, graduated_at) = match &ctx.accounts.bonding_curve
+    {
+        Some(bonding_curve) => {
+            require!(bonding_curve.completed, VestingError::NotGraduated);
+            // never on a sunset or re-opened curve: begin_migration refuses them
+            require!(bonding_curve.migrated, VestingError::NotMigrated);
+            (bonding_curve.creator, bonding_curve.completed_at)
+        }
+        None => {
+            require!(ctx.accounts.vesting.graduated_at > 0, VestingError::NotGraduated);
+            (ctx.accounts.vesting.creator, ctx.accounts.vesting.graduated_at)
+        }
+    };
+    require!(creator == ctx.accounts.creator.key(), VestingError::NotCreator);
+
+    let now = Clock::get()?.unix_timestamp;
+    let vested = ctx.accounts.vesting.vested_amount(graduated_at, now).ok_or(MathError::Overflow)?;
+    let amount = vested.checked_sub(ctx.accounts.vesting.claimed_amount).ok_or(MathError::Overflow)?;
+    require!(amount > 0, VestingError::NothingToClaim);
+
+    let mint_key = ctx.accounts.mint.key();
+    let seeds = &[
+        VESTING_SEED,
+        mint_key.as_ref(),
+        &[ctx.accounts.vesting.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    anchor_spl::token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            anchor_spl::token_interface::TransferChecked {
+                from: ctx.accounts.vesting_token_account.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.creator_token_account.to_account_info(),
+                authority: ctx.accounts.vesting.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount,
+        ctx.accounts.mint.decimals,
+    )?;
+
+    ctx.accounts.vesting.claimed_amount = vested;
+
+    emit!(VestingClaimed {
+        mint: ctx.accounts.mint.key(),
+        creator: ctx.accounts.creator.key(),
+        amount,
+        claimed_amount: vested,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ClaimVested<'info>
+{
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        mint::token_program = token_program,
+    )]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// None once finalize_migration closed it
+    #[account(
+        seeds = [BONDING_CURVE_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub bonding_curve: Option<Account<'info, BondingCurve>>,
+
+    #[account(
+        mut,
+        seeds = [VESTING_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub vesting: Account<'info, CreatorVesting>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vesting,
+        associated_token::token_program = token_program,
+    )]
+    pub vesting_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = creator,
+        associated_token::mint = mint,
+        associated_token::authority = creator,
+        associated_token::token_program = token_program,
+    )]
+    pub creator_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
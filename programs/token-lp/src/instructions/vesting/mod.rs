@@ -0,0 +1,3 @@
+pub mod claim_vested;
+
+pub use claim_vested::*;
@@ -21,20 +21,9 @@ pub mod token_lp {
         instructions::admin::initialize::_initialize(ctx)
     }
 
-    pub fn update_config(ctx: Context<UpdateConfig>, 
-        new_fee_receiver: Option<Pubkey>,
-        new_initial_virtual_sol_reserves: Option<u64>,
-        new_initial_virtual_token_reserves: Option<u64>,
-        new_initial_real_token_reserves: Option<u64>,
-        new_token_total_supply: Option<u64>,
-        new_trade_fee_bps: Option<u16>,
-        new_creator_share_bps: Option<u16>,
-        new_referral_share_bps: Option<u16>,
-        new_graduation_threshold: Option<u64>,
-        new_status: Option<ProgramStatus>,
-    ) -> Result<()>
-    {
-        instructions::admin::update_config::_update_config(ctx, new_fee_receiver, new_initial_virtual_sol_reserves, new_initial_virtual_token_reserves, new_initial_real_token_reserves, new_token_total_supply, new_trade_fee_bps, new_creator_share_bps, new_referral_share_bps, new_graduation_threshold, new_status)
+    pub fn update_config(ctx: Context<UpdateConfig>, params: UpdateConfigParams) -> Result<()>
+    {
+        instructions::admin::update_config::_update_config(ctx, params)
     }
 
     pub fn withdraw_fees(ctx: Context<WithdrawFees>) -> Result<()>
@@ -42,14 +31,34 @@ pub mod token_lp {
         instructions::admin::withdraw_fees::_withdraw_fees(ctx)
     }
 
-    pub fn create_token(ctx: Context<CreateToken>, name: String, symbol: String, uri: String) -> Result<()>
+    pub fn create_token(ctx: Context<CreateToken>, name: String, symbol: String, uri: String, referral_share_bps: Option<u16>, start_time: Option<i64>, creator_allocation_bps: Option<u16>, mutable_metadata: Option<bool>) -> Result<()>
+    {
+        instructions::launch::create_token::_create_token(ctx, name, symbol, uri, referral_share_bps, start_time, creator_allocation_bps, mutable_metadata)
+    }
+
+    pub fn create_token_2022(ctx: Context<CreateToken2022>, name: String, symbol: String, uri: String, referral_share_bps: Option<u16>, start_time: Option<i64>, creator_allocation_bps: Option<u16>, mutable_metadata: Option<bool>) -> Result<()>
+    {
+        instructions::launch::create_token_2022::_create_token_2022(ctx, name, symbol, uri, referral_share_bps, start_time, creator_allocation_bps, mutable_metadata)
+    }
+
+    pub fn create_and_buy_token(ctx: Context<CreateAndBuyToken>, name: String, symbol: String, uri: String, sol_amount: u64, min_tokens_out: u64, referral_share_bps: Option<u16>, start_time: Option<i64>, creator_allocation_bps: Option<u16>, mutable_metadata: Option<bool>) -> Result<()>
+    {
+        instructions::launch::create_and_buy::_create_and_buy_token(ctx, name, symbol, uri, sol_amount, min_tokens_out, referral_share_bps, start_time, creator_allocation_bps, mutable_metadata)
+    }
+
+    pub fn create_and_buy_token_2022(ctx: Context<CreateAndBuyToken2022>, name: String, symbol: String, uri: String, sol_amount: u64, min_tokens_out: u64, referral_share_bps: Option<u16>, start_time: Option<i64>, creator_allocation_bps: Option<u16>, mutable_metadata: Option<bool>) -> Result<()>
+    {
+        instructions::launch::create_and_buy_2022::_create_and_buy_token_2022(ctx, name, symbol, uri, sol_amount, min_tokens_out, referral_share_bps, start_time, creator_allocation_bps, mutable_metadata)
+    }
+
+    pub fn set_presale(ctx: Context<SetPresale>, merkle_root: [u8; 32], presale_end_time: i64) -> Result<()>
     {
-        instructions::launch::create_token::_create_token(ctx, name, symbol, uri)
+        instructions::launch::set_presale::_set_presale(ctx, merkle_root, presale_end_time)
     }
 
-    pub fn create_and_buy_token(ctx: Context<CreateAndBuyToken>, name: String, symbol: String, uri: String, sol_amount: u64, min_tokens_out: u64) -> Result<()>
+    pub fn cancel_launch(ctx: Context<CancelLaunch>) -> Result<()>
     {
-        instructions::launch::create_and_buy::_create_and_buy_token(ctx, name, symbol, uri, sol_amount, min_tokens_out)
+        instructions::launch::cancel_launch::_cancel_launch(ctx)
     }
 
     pub fn buy_token(ctx: Context<Buy>, sol_amount: u64, min_tokens_out: u64) -> Result<()>
@@ -57,11 +66,21 @@ pub mod token_lp {
         instructions::trade::buy::_buy(ctx, sol_amount, min_tokens_out)
     }
 
+    pub fn presale_buy(ctx: Context<Buy>, sol_amount: u64, min_tokens_out: u64, max_tokens: u64, proof: Vec<[u8; 32]>) -> Result<()>
+    {
+        instructions::trade::presale_buy::_presale_buy(ctx, sol_amount, min_tokens_out, max_tokens, proof)
+    }
+
     pub fn sell_token(ctx: Context<Sell>, token_amount: u64, min_sol_out: u64) -> Result<()>
     {
         instructions::trade::sell::_sell(ctx, token_amount, min_sol_out)
     }  
 
+    pub fn swap(ctx: Context<Swap>, is_buy: bool, amount_in: u64, min_amount_out: u64) -> Result<()>
+    {
+        instructions::trade::swap::_swap(ctx, is_buy, amount_in, min_amount_out)
+    }
+
     pub fn register_referral(ctx: Context<RegisterReferral>) -> Result<()>
     {
         instructions::referral::register_referral::_register_referral(ctx)
@@ -72,8 +91,63 @@ pub mod token_lp {
         instructions::referral::claim_fees::_claim_referral_fees(ctx)
     }
 
+    pub fn claim_vested(ctx: Context<ClaimVested>) -> Result<()>
+    {
+        instructions::vesting::claim_vested::_claim_vested(ctx)
+    }
+
+    pub fn transfer_creator(ctx: Context<TransferCreator>, new_creator: Pubkey) -> Result<()>
+    {
+        instructions::creator::transfer_creator::_transfer_creator(ctx, new_creator)
+    }
+
+    pub fn accept_creator(ctx: Context<AcceptCreator>) -> Result<()>
+    {
+        instructions::creator::accept_creator::_accept_creator(ctx)
+    }
+
+    pub fn update_token_uri(ctx: Context<UpdateTokenUri>, uri: String) -> Result<()>
+    {
+        instructions::creator::update_token_uri::_update_token_uri(ctx, uri)
+    }
+
+    pub fn sunset_curve(ctx: Context<SunsetCurve>) -> Result<()>
+    {
+        instructions::sunset::sunset_curve::_sunset_curve(ctx)
+    }
+
+    pub fn reopen_curve(ctx: Context<ReopenCurve>) -> Result<()>
+    {
+        instructions::sunset::reopen_curve::_reopen_curve(ctx)
+    }
+
+    pub fn redeem_sunset(ctx: Context<RedeemSunset>, token_amount: u64) -> Result<()>
+    {
+        instructions::sunset::redeem_sunset::_redeem_sunset(ctx, token_amount)
+    }
+
+    pub fn close_sunset_curve(ctx: Context<CloseSunsetCurve>) -> Result<()>
+    {
+        instructions::sunset::close_sunset_curve::_close_sunset_curve(ctx)
+    }
+
     pub fn migrate_to_raydium(ctx: Context<MigrateRaydium>) -> Result<()>
     {
         instructions::migration::migrate_to_raydium::_migrate_to_raydium(ctx)
     }
+
+    pub fn buy_and_migrate(ctx: Context<BuyAndMigrate>, sol_amount: u64, min_tokens_out: u64) -> Result<()>
+    {
+        instructions::migration::buy_and_migrate::_buy_and_migrate(ctx, sol_amount, min_tokens_out)
+    }
+
+    pub fn harvest_lp_fees(ctx: Context<HarvestLpFees>) -> Result<()>
+    {
+        instructions::migration::harvest_lp_fees::_harvest_lp_fees(ctx)
+    }
+
+    pub fn finalize_migration(ctx: Context<FinalizeMigration>) -> Result<()>
+    {
+        instructions::migration::finalize_migration::_finalize_migration(ctx)
+    }
 }
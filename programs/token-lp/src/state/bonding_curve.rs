@@ -6,13 +6,67 @@ pub struct BondingCurve
 {
     pub mint: Pubkey,
     pub creator: Pubkey,
+    pub pending_creator: Option<Pubkey>, // nominated by transfer_creator, until accept_creator
     pub virtual_sol: u64,
     pub virtual_token: u64,
     pub real_token: u64,
     pub real_sol_reserves: u64,
     pub token_total_supply: u64,
     pub start_time: i64,
+    pub presale_end_time: i64,          // 0 = no presale
+    pub presale_merkle_root: [u8; 32],
+    pub referral_share_bps: u16,
+    pub launch_referrer: Option<Pubkey>, // referrer passed at creation, shares the locked LP fees
+    pub creator_allocation: u64,
+    pub metadata_mutable: bool,         // URI updatable by the creator until migration
+    pub supply_locked: bool,            // mint authority revoked, supply can never grow
+    pub last_trade_time: i64,           // 0 until the first trade
+    pub sunset: bool,                   // inactive curve closed to trading, holders redeem pro-rata
+    pub sells_reopened: bool,           // completed but never migrated, sells re-opened by the authority
     pub completed: bool,
+    pub completed_at: i64,
     pub migrated: bool,
+    pub pool: Pubkey,                   // set at migration
+    pub migrated_at: i64,
+    pub pool_sol_amount: u64,           // liquidity seeded at migration
+    pub pool_token_amount: u64,
     pub bump: u8,
-}
\ No newline at end of file
+}
+
+impl BondingCurve
+{
+    /// The allowlisted presale runs from start_time to presale_end_time
+    pub fn presale_active(&self, now: i64) -> bool
+    {
+        now >= self.start_time && now < self.presale_end_time
+    }
+
+    /// Completed, still not migrated `timeout` seconds later (0 = never): the escape hatch opens
+    pub fn migration_timed_out(&self, timeout: i64, now: i64) -> bool
+    {
+        self.completed && !self.migrated && timeout > 0 && now >= self.completed_at.saturating_add(timeout)
+    }
+
+    /// Tokens held by traders: everything minted but the curve vault and the (never vested) creator allocation
+    pub fn outstanding_tokens(&self, mint_supply: u64, vault_amount: u64) -> Option<u64>
+    {
+        mint_supply.checked_sub(vault_amount)?.checked_sub(self.creator_allocation)
+    }
+
+    /// Fee-free pro-rata share of real_sol_reserves for `amount` out of the `outstanding` holder tokens
+    pub fn redemption_amount(&self, amount: u64, outstanding: u64) -> Option<u64>
+    {
+        let sol = (self.real_sol_reserves as u128)
+            .checked_mul(amount as u128)?
+            .checked_div(outstanding as u128)?;
+        u64::try_from(sol).ok()
+    }
+}
+
+/// Why a curve was sunset
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SunsetReason
+{
+    Inactive,         // no trade for sunset_inactivity
+    MigrationTimeout, // completed but not migrated within migration_timeout
+}
@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+
+/// Allocation de tokens réservée au creator, prise sur le total supply.
+///
+/// Les tokens sont lockés dans l'ATA du PDA jusqu'à la migration, puis
+/// relâchés linéairement sur `duration` secondes après un `cliff`, comptés
+/// depuis la graduation. Jamais sur une curve sunset ou réouverte aux sells.
+///
+/// PDA seeds: ["vesting", mint.key().as_ref()]
+#[account]
+#[derive(InitSpace)]
+pub struct CreatorVesting {
+    pub mint: Pubkey,
+    pub total_amount: u64,      // tokens alloués au creator
+    pub claimed_amount: u64,    // déjà claim
+    pub cliff: i64,             // secondes après la graduation
+    pub duration: i64,          // durée du release linéaire, à partir de la graduation
+    pub creator: Pubkey,        // copié de la curve par finalize_migration, qui la ferme
+    pub graduated_at: i64,      // idem, 0 tant que la curve existe
+    pub bump: u8,
+}
+
+impl CreatorVesting {
+    /// Tokens débloqués à `now` pour une curve graduée à `start`
+    pub fn vested_amount(&self, start: i64, now: i64) -> Option<u64> {
+        let elapsed = now.checked_sub(start)?;
+        if elapsed < self.cliff {
+            return Some(0);
+        }
+        if elapsed >= self.duration {
+            return Some(self.total_amount);
+        }
+        let vested = (self.total_amount as u128)
+            .checked_mul(elapsed as u128)?
+            .checked_div(self.duration as u128)?;
+        u64::try_from(vested).ok()
+    }
+}
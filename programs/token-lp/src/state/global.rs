@@ -14,16 +14,146 @@ pub struct Global
     pub token_decimal: u8,
     pub trade_fee_bps: u16,
     pub creator_share_bps: u16,
-    pub referral_share_bps: u16,
-    pub graduation_threshold: u64,
+    pub referral_share_bps: u16,         // default per-curve share, taken from the creator's cut
+    pub min_referral_share_bps: u16,
+    pub max_referral_share_bps: u16,
+    pub creation_fee: u64,               // lamports paid into fee_vault per launch
+    pub creation_referral_share_bps: u16, // of creation_fee, paid to the referrer if any
+    pub graduation_threshold: u64,       // real SOL reserves, RealSol mode
+    pub graduation_mode: GraduationMode,
+    pub graduation_market_cap: u64,      // lamports, MarketCap mode
+    pub migration_fee: MigrationFee,     // taken off the reserves at migration
+    pub migration_crank_reward: u64,     // of the migration fee, paid to the migrate caller
+    pub migration_venue: MigrationVenue, // where completed curves graduate to
+    pub lp_mode: LpMode,                 // burn the pool LP or lock it and harvest its fees
+    pub lp_fee_creator_share_bps: u16,   // of harvested LP fees, the rest to the protocol
+    pub lp_fee_referral_share_bps: u16,  // of harvested LP fees, to the launch referrer if any
+    pub leftover_mode: LeftoverMode,     // vault tokens not needed to match the curve price at migration
+    pub migration_price_tolerance_bps: u16, // max deviation of the pool opening price from the curve price
+    pub keep_graduation_record: bool,    // finalize_migration leaves a GraduationRecord behind the closed curve
+    pub finalize_rent_destination: RentDestination, // who gets the curve rent back at finalize_migration
+    pub max_start_delay: i64,            // seconds a launch can be scheduled ahead
+    pub max_presale_duration: i64,       // seconds after start_time reserved to the allowlist
+    pub opening_window: i64,             // seconds after start_time with per-wallet caps
+    pub max_wallet_buy_bps: u16,         // per-wallet cap during the window, of total supply
+    pub max_creator_allocation_bps: u16, // of total supply, vested to the creator
+    pub vesting_cliff: i64,
+    pub vesting_duration: i64,
+    pub sunset_inactivity: i64,          // seconds without trades before a curve can be sunset, 0 = disabled
+    pub migration_timeout: i64,          // seconds after completion before a stuck curve can be reopened or sunset, 0 = disabled
     pub status: ProgramStatus,
     pub bump: u8,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+impl Global
+{
+    /// Migration fee for a curve holding `real_sol_reserves`
+    pub fn migration_fee_for(&self, real_sol_reserves: u64) -> Option<u64>
+    {
+        match self.migration_fee
+        {
+            MigrationFee::Fixed { lamports } => Some(lamports),
+            MigrationFee::Bps { bps } => {
+                let fee = (real_sol_reserves as u128)
+                    .checked_mul(bps as u128)?
+                    .checked_div(10_000)?;
+                u64::try_from(fee).ok()
+            }
+        }
+    }
+}
+
+/// update_config settings, `None` leaves the current value
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct UpdateConfigParams
+{
+    pub fee_receiver: Option<Pubkey>,
+    pub initial_virtual_sol_reserves: Option<u64>,
+    pub initial_virtual_token_reserves: Option<u64>,
+    pub initial_real_token_reserves: Option<u64>,
+    pub token_total_supply: Option<u64>,
+    pub trade_fee_bps: Option<u16>,
+    pub creator_share_bps: Option<u16>,
+    pub referral_share_bps: Option<u16>,
+    pub graduation_threshold: Option<u64>,
+    pub status: Option<ProgramStatus>,
+    pub min_referral_share_bps: Option<u16>,
+    pub max_referral_share_bps: Option<u16>,
+    pub max_start_delay: Option<i64>,
+    pub opening_window: Option<i64>,
+    pub max_wallet_buy_bps: Option<u16>,
+    pub max_creator_allocation_bps: Option<u16>,
+    pub vesting_cliff: Option<i64>,
+    pub vesting_duration: Option<i64>,
+    pub max_presale_duration: Option<i64>,
+    pub creation_fee: Option<u64>,
+    pub creation_referral_share_bps: Option<u16>,
+    pub sunset_inactivity: Option<i64>,
+    pub migration_crank_reward: Option<u64>,
+    pub migration_venue: Option<MigrationVenue>,
+    pub lp_mode: Option<LpMode>,
+    pub lp_fee_creator_share_bps: Option<u16>,
+    pub lp_fee_referral_share_bps: Option<u16>,
+    pub leftover_mode: Option<LeftoverMode>,
+    pub migration_price_tolerance_bps: Option<u16>,
+    pub migration_fee: Option<MigrationFee>,
+    pub keep_graduation_record: Option<bool>,
+    pub finalize_rent_destination: Option<RentDestination>,
+    pub graduation_mode: Option<GraduationMode>,
+    pub graduation_market_cap: Option<u64>,
+    pub migration_timeout: Option<i64>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
 pub enum ProgramStatus
 {
     Running,
     SwapOnly,
     Paused,
-}
\ No newline at end of file
+}
+/// What completes a curve, checked after every buy
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum GraduationMode
+{
+    RealSol,    // real_sol_reserves >= graduation_threshold
+    MarketCap,  // price * token_total_supply >= graduation_market_cap
+    TokensSold, // real_token all sold
+}
+
+/// Graduation target, one variant per venue with a migrate instruction
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum MigrationVenue
+{
+    RaydiumCpmm,
+}
+
+/// What happens to the pool LP at migration
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum LpMode
+{
+    Burn,
+    Lock,
+}
+
+/// What happens to the vault tokens left over once the pool is seeded at the curve price
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum LeftoverMode
+{
+    Burn,
+    Protocol, // sent to the fee_receiver's token account
+}
+
+/// Who gets the rent of the accounts closed by finalize_migration
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum RentDestination
+{
+    FeeVault,
+    Creator,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum MigrationFee
+{
+    Fixed { lamports: u64 },
+    Bps { bps: u16 }, // of real_sol_reserves at migration
+}
@@ -0,0 +1,18 @@
+use anchor_lang::prelude::*;
+
+/// Trace compacte d'une curve graduée, laissée par `finalize_migration`
+/// à la place du `BondingCurve` fermé (si `Global.keep_graduation_record`).
+///
+/// PDA seeds: ["graduation", mint.key().as_ref()]
+#[account]
+#[derive(InitSpace)]
+pub struct GraduationRecord {
+    pub mint: Pubkey,
+    pub creator: Pubkey,        // creator de la curve à la fermeture, figé : plus de transfer_creator sans la curve
+    pub pool: Pubkey,
+    pub referral_share_bps: u16,    // du creator, pour le fee skim de `swap` sur la pool
+    pub migrated_at: i64,
+    pub sol_amount: u64,        // liquidité déposée dans la pool à la migration
+    pub token_amount: u64,
+    pub bump: u8,
+}
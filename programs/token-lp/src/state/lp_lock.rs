@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use crate::utils::math::sqrt_u128;
+
+/// LP d'une curve graduée, lockée pour toujours au lieu d'être burn.
+///
+/// Le principal ne sort jamais : `harvest_lp_fees` ne retire que la part de LP
+/// correspondant aux fees accumulées au-dessus de `locked_liquidity`.
+///
+/// PDA seeds: ["lp-lock", mint.key().as_ref()]
+#[account]
+#[derive(InitSpace)]
+pub struct LpLock {
+    pub mint: Pubkey,
+    pub pool_state: Pubkey,
+    pub lp_mint: Pubkey,
+    pub creator: Pubkey,            // creator de la curve, suivi par accept_creator
+    pub referrer: Option<Pubkey>,   // launch referrer de la curve, s'il y en a un
+    pub lp_amount: u64,             // LP encore lockée
+    pub locked_liquidity: u128,     // sqrt(reserve_0 * reserve_1) de lp_amount au moment du lock
+    pub bump: u8,
+}
+
+impl LpLock {
+    /// Liquidité (en sqrt(k)) représentée par `lp_amount` sur `lp_supply`
+    pub fn liquidity_of(reserve_0: u64, reserve_1: u64, lp_amount: u64, lp_supply: u64) -> Option<u128> {
+        let k = (reserve_0 as u128).checked_mul(reserve_1 as u128)?;
+        sqrt_u128(k)
+            .checked_mul(lp_amount as u128)?
+            .checked_div(lp_supply as u128)
+    }
+
+    /// LP à retirer pour ne sortir que les fees : lp_amount * (current - locked) / current
+    pub fn harvestable_lp(&self, current_liquidity: u128) -> Option<u64> {
+        if current_liquidity <= self.locked_liquidity {
+            return Some(0);
+        }
+        let lp = (self.lp_amount as u128)
+            .checked_mul(current_liquidity.checked_sub(self.locked_liquidity)?)?
+            .checked_div(current_liquidity)?;
+        u64::try_from(lp).ok()
+    }
+}
@@ -1,6 +1,14 @@
 pub mod global;
 pub mod bonding_curve;
 pub mod referral;
+pub mod wallet_purchase;
+pub mod creator_vesting;
+pub mod lp_lock;
+pub mod graduation_record;
 pub use global::*;
 pub use bonding_curve::*;
-pub use referral::*;
\ No newline at end of file
+pub use referral::*;
+pub use wallet_purchase::*;
+pub use creator_vesting::*;
+pub use lp_lock::*;
+pub use graduation_record::*;
\ No newline at end of file
@@ -0,0 +1,15 @@
+use anchor_lang::prelude::*;
+
+/// Cumulative buys of one wallet on one curve.
+///
+/// Used to cap how much a single wallet can buy during the opening window.
+///
+/// PDA seeds: ["wallet-purchase", mint.key().as_ref(), wallet.key().as_ref()]
+#[account]
+#[derive(InitSpace)]
+pub struct WalletPurchase {
+    pub wallet: Pubkey,
+    pub mint: Pubkey,
+    pub tokens_bought: u64,     // total tokens bought (sells don't reduce it)
+    pub bump: u8,
+}
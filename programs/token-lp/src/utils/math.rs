@@ -30,3 +30,38 @@ pub fn calculate_sell_amount(virtual_sol: u64, virtual_token: u64, token_amount:
 
     u64::try_from(sol_out).map_err(|_| MathError::CastOverflow.into())
 }
+
+/// SOL (après fee) à payer pour recevoir au moins `token_amount` tokens, arrondi vers le haut
+/// Inverse de calculate_buy_amount : sol = (virtual_sol * token_amount) / (virtual_token - token_amount)
+pub fn calculate_sol_for_tokens(virtual_sol: u64, virtual_token: u64, token_amount: u64) -> Result<u64>
+{
+    let numerator = (virtual_sol as u128).checked_mul(token_amount as u128)
+    .ok_or(MathError::Overflow)?;
+
+    let denominator = (virtual_token as u128).checked_sub(token_amount as u128)
+    .ok_or(MathError::Overflow)?;
+
+    let sol_in = numerator.checked_add(denominator.checked_sub(1).ok_or(MathError::DivisionByZero)?)
+    .ok_or(MathError::Overflow)?
+    .checked_div(denominator)
+    .ok_or(MathError::DivisionByZero)?;
+
+    u64::try_from(sol_in).map_err(|_| MathError::CastOverflow.into())
+}
+
+/// Racine carrée entière (arrondie vers le bas), Newton
+pub fn sqrt_u128(n: u128) -> u128
+{
+    if n < 2
+    {
+        return n;
+    }
+    let mut x = n;
+    let mut y = (x + n / x) / 2;
+    while y < x
+    {
+        x = y;
+        y = (x + n / x) / 2;
+    }
+    x
+}
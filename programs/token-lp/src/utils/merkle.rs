@@ -0,0 +1,22 @@
+use solana_sha256_hasher::hashv;
+
+/// Leaf d'un wallet de l'allowlist : sha256(wallet || max_tokens LE)
+pub fn presale_leaf(wallet: &[u8], max_tokens: u64) -> [u8; 32]
+{
+    hashv(&[wallet, &max_tokens.to_le_bytes()]).to_bytes()
+}
+
+/// Vérifie une proof merkle (paires triées, sha256)
+pub fn verify_proof(proof: &[[u8; 32]], root: [u8; 32], leaf: [u8; 32]) -> bool
+{
+    let mut computed = leaf;
+    for node in proof
+    {
+        computed = if computed <= *node {
+            hashv(&[&computed, node]).to_bytes()
+        } else {
+            hashv(&[node, &computed]).to_bytes()
+        };
+    }
+    computed == root
+}
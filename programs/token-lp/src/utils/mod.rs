@@ -1 +1,3 @@
 pub mod math;
+pub mod merkle;
+pub mod validation;
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use anchor_spl::metadata::mpl_token_metadata::{MAX_NAME_LENGTH, MAX_SYMBOL_LENGTH, MAX_URI_LENGTH};
+use crate::errors::*;
+
+pub const ALLOWED_URI_SCHEMES: [&str; 3] = ["https://", "ipfs://", "ar://"];
+
+/// Name : ASCII imprimable, trim, 1..=32 bytes
+pub fn validate_name(name: &str) -> Result<String>
+{
+    let name = name.trim();
+    require!(!name.is_empty(), LaunchError::InvalidName);
+    require!(name.len() <= MAX_NAME_LENGTH, LaunchError::NameTooLong);
+    require!(name.bytes().all(|b| (0x20..=0x7e).contains(&b)), LaunchError::InvalidName);
+    Ok(name.to_string())
+}
+
+/// Symbol : trim, `$` optionnel retiré, uppercase, A-Z / 0-9 uniquement, 1..=10 bytes
+pub fn normalize_symbol(symbol: &str) -> Result<String>
+{
+    let symbol = symbol.trim();
+    let symbol = symbol.strip_prefix('$').unwrap_or(symbol).to_ascii_uppercase();
+    require!(!symbol.is_empty(), LaunchError::InvalidSymbol);
+    require!(symbol.len() <= MAX_SYMBOL_LENGTH, LaunchError::SymbolTooLong);
+    require!(symbol.bytes().all(|b| b.is_ascii_uppercase() || b.is_ascii_digit()), LaunchError::InvalidSymbol);
+    Ok(symbol)
+}
+
+/// URI : https / ipfs / ar, sans espaces ni caractères de contrôle, <= 200 bytes
+pub fn validate_uri(uri: &str) -> Result<String>
+{
+    let uri = uri.trim();
+    require!(uri.len() <= MAX_URI_LENGTH, LaunchError::UriTooLong);
+    require!(uri.bytes().all(|b| (0x21..=0x7e).contains(&b)), LaunchError::InvalidUri);
+    let scheme = ALLOWED_URI_SCHEMES.iter().find(|s| uri.starts_with(*s)).ok_or(LaunchError::UnsupportedUriScheme)?;
+    require!(uri.len() > scheme.len(), LaunchError::InvalidUri);
+    Ok(uri.to_string())
+}
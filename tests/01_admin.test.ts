@@ -16,7 +16,12 @@ import {
   DEFAULT_TRADE_FEE_BPS,
   DEFAULT_CREATOR_SHARE_BPS,
   DEFAULT_REFERRAL_SHARE_BPS,
+  DEFAULT_MIN_REFERRAL_SHARE_BPS,
+  DEFAULT_MAX_REFERRAL_SHARE_BPS,
   DEFAULT_GRADUATION_THRESHOLD,
+  DEFAULT_MIGRATION_FEE,
+  DEFAULT_MIGRATION_CRANK_REWARD,
+  configParams,
 } from "./helpers";
 import { getGlobalPda, getFeeVaultPda } from "./helpers/pda";
 
@@ -49,7 +54,22 @@ describe("01 - Admin", () => {
       expect(global.tradeFeeBps).to.equal(DEFAULT_TRADE_FEE_BPS);
       expect(global.creatorShareBps).to.equal(DEFAULT_CREATOR_SHARE_BPS);
       expect(global.referralShareBps).to.equal(DEFAULT_REFERRAL_SHARE_BPS);
+      expect(global.minReferralShareBps).to.equal(DEFAULT_MIN_REFERRAL_SHARE_BPS);
+      expect(global.maxReferralShareBps).to.equal(DEFAULT_MAX_REFERRAL_SHARE_BPS);
       expect(global.graduationThreshold.toString()).to.equal(DEFAULT_GRADUATION_THRESHOLD.toString());
+      expect(global.graduationMode).to.deep.equal({ realSol: {} });
+      expect(global.graduationMarketCap.toString()).to.equal(new anchor.BN(400 * LAMPORTS_PER_SOL).toString());
+      expect(global.migrationFee.fixed!.lamports.toString()).to.equal(DEFAULT_MIGRATION_FEE.toString());
+      expect(global.migrationCrankReward.toString()).to.equal(DEFAULT_MIGRATION_CRANK_REWARD.toString());
+      expect(global.migrationVenue).to.deep.equal({ raydiumCpmm: {} });
+      expect(global.lpMode).to.deep.equal({ burn: {} });
+      expect(global.lpFeeCreatorShareBps).to.equal(5000);
+      expect(global.lpFeeReferralShareBps).to.equal(0);
+      expect(global.leftoverMode).to.deep.equal({ burn: {} });
+      expect(global.migrationPriceToleranceBps).to.equal(100);
+      expect(global.keepGraduationRecord).to.be.true;
+      expect(global.finalizeRentDestination).to.deep.equal({ creator: {} });
+      expect(global.migrationTimeout.toNumber()).to.equal(7 * 24 * 60 * 60);
       expect(global.status).to.deep.equal({ running: {} });
     });
 
@@ -78,18 +98,11 @@ describe("01 - Admin", () => {
       const newThreshold = new anchor.BN(100 * LAMPORTS_PER_SOL);
 
       await program.methods
-        .updateConfig(
-          newFeeReceiver,     // new_fee_receiver
-          null,               // new_initial_virtual_sol_reserves
-          null,               // new_initial_virtual_token_reserves
-          null,               // new_initial_real_token_reserves
-          null,               // new_token_total_supply
-          200,                // new_trade_fee_bps (2%)
-          null,               // new_creator_share_bps
-          null,               // new_referral_share_bps
-          newThreshold,       // new_graduation_threshold
-          null,               // new_status
-        )
+        .updateConfig(configParams({
+          feeReceiver: newFeeReceiver,
+          tradeFeeBps: 200, // 2%
+          graduationThreshold: newThreshold,
+        }))
         .accounts({
           authority: authority.publicKey,
           global: globalPda,
@@ -103,18 +116,18 @@ describe("01 - Admin", () => {
 
       // Restore defaults for subsequent tests
       await program.methods
-        .updateConfig(
-          authority.publicKey,
-          DEFAULT_VIRTUAL_SOL,
-          DEFAULT_VIRTUAL_TOKENS,
-          DEFAULT_REAL_TOKENS,
-          DEFAULT_TOKEN_SUPPLY,
-          DEFAULT_TRADE_FEE_BPS,
-          DEFAULT_CREATOR_SHARE_BPS,
-          DEFAULT_REFERRAL_SHARE_BPS,
-          DEFAULT_GRADUATION_THRESHOLD,
-          { running: {} },
-        )
+        .updateConfig(configParams({
+          feeReceiver: authority.publicKey,
+          initialVirtualSolReserves: DEFAULT_VIRTUAL_SOL,
+          initialVirtualTokenReserves: DEFAULT_VIRTUAL_TOKENS,
+          initialRealTokenReserves: DEFAULT_REAL_TOKENS,
+          tokenTotalSupply: DEFAULT_TOKEN_SUPPLY,
+          tradeFeeBps: DEFAULT_TRADE_FEE_BPS,
+          creatorShareBps: DEFAULT_CREATOR_SHARE_BPS,
+          referralShareBps: DEFAULT_REFERRAL_SHARE_BPS,
+          graduationThreshold: DEFAULT_GRADUATION_THRESHOLD,
+          status: { running: {} },
+        }))
         .accounts({
           authority: authority.publicKey,
           global: globalPda,
@@ -122,16 +135,183 @@ describe("01 - Admin", () => {
         .rpc();
     });
 
+    it("should reject a crank reward above the migration fee", async () => {
+      try {
+        await program.methods
+          .updateConfig(configParams({ migrationCrankReward: DEFAULT_MIGRATION_FEE.addn(1) }))
+          .accounts({
+            authority: authority.publicKey,
+            global: globalPda,
+          })
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.satisfy(
+          (s: string) => s.includes("InvalidConfigParam") || s.includes("Invalid config parameter")
+        );
+      }
+    });
+
+    it("should set the migration venue", async () => {
+      await program.methods
+        .updateConfig(configParams({ migrationVenue: { raydiumCpmm: {} } }))
+        .accounts({
+          authority: authority.publicKey,
+          global: globalPda,
+        })
+        .rpc();
+
+      const global = await program.account.global.fetch(globalPda);
+      expect(global.migrationVenue).to.deep.equal({ raydiumCpmm: {} });
+    });
+
+    it("should reject LP fee shares above 100%", async () => {
+      try {
+        await program.methods
+          .updateConfig(configParams({ lpFeeCreatorShareBps: 8000, lpFeeReferralShareBps: 3000 }))
+          .accounts({
+            authority: authority.publicKey,
+            global: globalPda,
+          })
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.satisfy(
+          (s: string) => s.includes("InvalidConfigParam") || s.includes("Invalid config parameter")
+        );
+      }
+    });
+
+    it("should reject a migration price tolerance above 100%", async () => {
+      try {
+        await program.methods
+          .updateConfig(configParams({ migrationPriceToleranceBps: 10_001 }))
+          .accounts({
+            authority: authority.publicKey,
+            global: globalPda,
+          })
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.satisfy(
+          (s: string) => s.includes("InvalidConfigParam") || s.includes("Invalid config parameter")
+        );
+      }
+    });
+
+    it("should reject a zero graduation market cap", async () => {
+      try {
+        await program.methods
+          .updateConfig(configParams({ graduationMarketCap: new anchor.BN(0) }))
+          .accounts({
+            authority: authority.publicKey,
+            global: globalPda,
+          })
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.satisfy(
+          (s: string) => s.includes("InvalidConfigParam") || s.includes("Invalid config parameter")
+        );
+      }
+    });
+
+    it("should switch the graduation mode to market cap and back", async () => {
+      await program.methods
+        .updateConfig(configParams({ graduationMode: { marketCap: {} } }))
+        .accounts({ authority: authority.publicKey, global: globalPda })
+        .rpc();
+      let global = await program.account.global.fetch(globalPda);
+      expect(global.graduationMode).to.deep.equal({ marketCap: {} });
+
+      await program.methods
+        .updateConfig(configParams({ graduationMode: { realSol: {} } }))
+        .accounts({ authority: authority.publicKey, global: globalPda })
+        .rpc();
+      global = await program.account.global.fetch(globalPda);
+      expect(global.graduationMode).to.deep.equal({ realSol: {} });
+    });
+
+    it("should reject a migration fee the graduation threshold can't cover", async () => {
+      try {
+        await program.methods
+          .updateConfig(configParams({ migrationFee: { fixed: { lamports: DEFAULT_GRADUATION_THRESHOLD } } }))
+          .accounts({
+            authority: authority.publicKey,
+            global: globalPda,
+          })
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.satisfy(
+          (s: string) => s.includes("InvalidConfigParam") || s.includes("Invalid config parameter")
+        );
+      }
+    });
+
+    it("should reject a market cap the migration fee can't be paid out of", async () => {
+      // the curve starts at ~28 SOL of market cap: it would graduate on its first lamports
+      try {
+        await program.methods
+          .updateConfig(configParams({ graduationMode: { marketCap: {} }, graduationMarketCap: new anchor.BN(28 * LAMPORTS_PER_SOL) }))
+          .accounts({
+            authority: authority.publicKey,
+            global: globalPda,
+          })
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.satisfy(
+          (s: string) => s.includes("InvalidConfigParam") || s.includes("Invalid config parameter")
+        );
+      }
+      const global = await program.account.global.fetch(globalPda);
+      expect(global.graduationMode).to.deep.equal({ realSol: {} });
+    });
+
+    it("should reject a max creator allocation the vault of a sold-out curve can't spare", async () => {
+      // the default curve pairs its ~85 SOL with all but ~0.1% of the vault
+      try {
+        await program.methods
+          .updateConfig(configParams({ maxCreatorAllocationBps: 100 }))
+          .accounts({
+            authority: authority.publicKey,
+            global: globalPda,
+          })
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.satisfy(
+          (s: string) => s.includes("InvalidConfigParam") || s.includes("Invalid config parameter")
+        );
+      }
+    });
+
+    it("should switch the migration fee to a share of the reserves and back", async () => {
+      await program.methods
+        .updateConfig(configParams({ migrationFee: { bps: { bps: 100 } } }))
+        .accounts({ authority: authority.publicKey, global: globalPda })
+        .rpc();
+
+      let global = await program.account.global.fetch(globalPda);
+      expect(global.migrationFee.bps!.bps).to.equal(100);
+
+      await program.methods
+        .updateConfig(configParams({ migrationFee: { fixed: { lamports: DEFAULT_MIGRATION_FEE } } }))
+        .accounts({ authority: authority.publicKey, global: globalPda })
+        .rpc();
+
+      global = await program.account.global.fetch(globalPda);
+      expect(global.migrationFee.fixed!.lamports.toString()).to.equal(DEFAULT_MIGRATION_FEE.toString());
+    });
+
     it("should fail when called by non-authority", async () => {
       const attacker = Keypair.generate();
       await airdrop(attacker.publicKey, 2 * LAMPORTS_PER_SOL);
 
       try {
         await program.methods
-          .updateConfig(
-            null, null, null, null, null, null, null, null, null,
-            { paused: {} },
-          )
+          .updateConfig(configParams({ status: { paused: {} } }))
           .accounts({
             authority: attacker.publicKey,
             global: globalPda,
@@ -6,11 +6,15 @@ import {
   connection,
   airdrop,
   createToken,
+  createToken2022,
+  buyToken,
+  sellToken,
   Keypair,
   PublicKey,
   SystemProgram,
   LAMPORTS_PER_SOL,
   TOKEN_PROGRAM_ID,
+  TOKEN_2022_PROGRAM_ID,
   ASSOCIATED_TOKEN_PROGRAM_ID,
   TOKEN_METADATA_PROGRAM_ID,
   getAssociatedTokenAddress,
@@ -19,8 +23,14 @@ import {
   DEFAULT_VIRTUAL_TOKENS,
   DEFAULT_REAL_TOKENS,
   DEFAULT_TOKEN_SUPPLY,
+  DEFAULT_MAX_START_DELAY,
+  configParams,
+  createAndBuyToken,
+  allowCreatorAllocations,
+  ALLOCATION_MAX_BPS,
 } from "./helpers";
-import { getGlobalPda, getBondingCurvePda, getFeeVaultPda } from "./helpers/pda";
+import { getGlobalPda, getBondingCurvePda, getFeeVaultPda, getWalletPurchasePda } from "./helpers/pda";
+import { getMint, getTokenMetadata } from "@solana/spl-token";
 
 describe("02 - Launch", () => {
   const authority = (provider.wallet as anchor.Wallet).payer;
@@ -52,6 +62,13 @@ describe("02 - Launch", () => {
       // Verify token supply was minted to the bonding curve's token account
       const tokenBalance = await connection.getTokenAccountBalance(tokenAccount);
       expect(tokenBalance.value.amount).to.equal(DEFAULT_TOKEN_SUPPLY.toString());
+
+      // Fixed supply: mint and freeze authorities revoked
+      expect(bc.supplyLocked).to.be.true;
+      const mintInfo = await getMint(connection, mint.publicKey);
+      expect(mintInfo.mintAuthority).to.be.null;
+      expect(mintInfo.freezeAuthority).to.be.null;
+      expect(mintInfo.supply.toString()).to.equal(DEFAULT_TOKEN_SUPPLY.toString());
     });
 
     it("should fail to create token when program is paused", async () => {
@@ -59,10 +76,7 @@ describe("02 - Launch", () => {
 
       // Pause the program
       await program.methods
-        .updateConfig(
-          null, null, null, null, null, null, null, null, null,
-          { paused: {} },
-        )
+        .updateConfig(configParams({ status: { paused: {} } }))
         .accounts({
           authority: authority.publicKey,
           global: globalPda,
@@ -82,10 +96,7 @@ describe("02 - Launch", () => {
       } finally {
         // Restore running state
         await program.methods
-          .updateConfig(
-            null, null, null, null, null, null, null, null, null,
-            { running: {} },
-          )
+          .updateConfig(configParams({ status: { running: {} } }))
           .accounts({
             authority: authority.publicKey,
             global: globalPda,
@@ -93,6 +104,239 @@ describe("02 - Launch", () => {
           .rpc();
       }
     });
+
+    it("should reject a referral share outside the global bounds", async () => {
+      const creator = Keypair.generate();
+      await airdrop(creator.publicKey, 10 * LAMPORTS_PER_SOL);
+
+      try {
+        // max_referral_share_bps defaults to 5000
+        await createToken(creator, "Greedy Token", "GRDY", "https://example.com/grdy.json", 6000);
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.satisfy(
+          (s: string) => s.includes("InvalidReferralShare") || s.includes("Referral share out of bounds")
+        );
+      }
+    });
+  });
+
+  describe("metadata validation", () => {
+    async function expectLaunchError(name: string, symbol: string, uri: string, codes: string[]) {
+      const creator = Keypair.generate();
+      await airdrop(creator.publicKey, 10 * LAMPORTS_PER_SOL);
+      try {
+        await createToken(creator, name, symbol, uri);
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.satisfy((s: string) => codes.some((c) => s.includes(c)));
+      }
+    }
+
+    it("should trim and uppercase the symbol", async () => {
+      const creator = Keypair.generate();
+      await airdrop(creator.publicKey, 10 * LAMPORTS_PER_SOL);
+
+      const { metadata } = await createToken(creator, "  Pepe Token  ", " $pepe ", "ipfs://bafybeigdyrzt");
+
+      // Metaplex layout: key(1) + update_authority(32) + mint(32) + name(4 + n) + symbol(4 + n)
+      const data = (await connection.getAccountInfo(metadata))!.data;
+      const nameLen = data.readUInt32LE(65);
+      const name = data.subarray(69, 69 + nameLen).toString().replace(/\0/g, "");
+      const symbolLen = data.readUInt32LE(69 + nameLen);
+      const symbol = data.subarray(73 + nameLen, 73 + nameLen + symbolLen).toString().replace(/\0/g, "");
+      expect(name).to.equal("Pepe Token");
+      expect(symbol).to.equal("PEPE");
+    });
+
+    it("should reject a name longer than 32 bytes", async () => {
+      await expectLaunchError("A".repeat(33), "LONG", "https://example.com/long.json", ["NameTooLong", "Token name too long"]);
+    });
+
+    it("should reject control characters in the name", async () => {
+      await expectLaunchError("Bad\u0007Name", "BAD", "https://example.com/bad.json", ["InvalidName", "Token name is empty"]);
+    });
+
+    it("should reject a symbol with lookalike characters", async () => {
+      await expectLaunchError("Lookalike", "PEPE\u0415", "https://example.com/look.json", ["InvalidSymbol", "Token symbol must be"]);
+    });
+
+    it("should reject an unsupported URI scheme", async () => {
+      await expectLaunchError("Http Token", "HTTP", "http://example.com/http.json", ["UnsupportedUriScheme", "Token URI must use"]);
+    });
+
+    it("should reject a URI longer than 200 bytes", async () => {
+      await expectLaunchError("Uri Token", "URI", "https://example.com/" + "a".repeat(200), ["UriTooLong", "Token URI too long"]);
+    });
+  });
+
+  describe("scheduled launch", () => {
+    it("should store a future start_time", async () => {
+      const creator = Keypair.generate();
+      await airdrop(creator.publicKey, 10 * LAMPORTS_PER_SOL);
+
+      const startTime = new anchor.BN(Math.floor(Date.now() / 1000) + 3600);
+      const { bondingCurvePda } = await createToken(creator, "Later Token", "LATER", "https://example.com/later.json", null, startTime);
+
+      const bc = await program.account.bondingCurve.fetch(bondingCurvePda);
+      expect(bc.startTime.toString()).to.equal(startTime.toString());
+    });
+
+    it("should reject a start_time beyond the max start delay", async () => {
+      const creator = Keypair.generate();
+      await airdrop(creator.publicKey, 10 * LAMPORTS_PER_SOL);
+
+      const startTime = new anchor.BN(Math.floor(Date.now() / 1000) + DEFAULT_MAX_START_DELAY + 3600);
+      try {
+        await createToken(creator, "Far Token", "FAR", "https://example.com/far.json", null, startTime);
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.satisfy(
+          (s: string) => s.includes("InvalidStartTime") || s.includes("Start time must be between")
+        );
+      }
+    });
+
+    it("should reject a start_time in the past", async () => {
+      const creator = Keypair.generate();
+      await airdrop(creator.publicKey, 10 * LAMPORTS_PER_SOL);
+
+      const startTime = new anchor.BN(Math.floor(Date.now() / 1000) - 3600);
+      try {
+        await createToken(creator, "Past Token", "PAST", "https://example.com/past.json", null, startTime);
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.satisfy(
+          (s: string) => s.includes("InvalidStartTime") || s.includes("Start time must be between")
+        );
+      }
+    });
+  });
+
+  describe("creator allocation", () => {
+    before(async () => {
+      await allowCreatorAllocations(true);
+    });
+
+    after(async () => {
+      await allowCreatorAllocations(false);
+    });
+
+    it("should mint the allocation to the vesting PDA", async () => {
+      const creator = Keypair.generate();
+      await airdrop(creator.publicKey, 10 * LAMPORTS_PER_SOL);
+
+      // 5% of total supply
+      const { bondingCurvePda, tokenAccount, vesting, vestingTokenAccount } = await createToken(
+        creator, "Vest Token", "VEST", "https://example.com/vest.json", null, null, 500
+      );
+
+      const allocation = DEFAULT_TOKEN_SUPPLY.muln(500).divn(10000);
+      const bc = await program.account.bondingCurve.fetch(bondingCurvePda);
+      expect(bc.creatorAllocation.toString()).to.equal(allocation.toString());
+
+      const vestingAccount = await program.account.creatorVesting.fetch(vesting!);
+      expect(vestingAccount.totalAmount.toString()).to.equal(allocation.toString());
+      expect(vestingAccount.claimedAmount.toNumber()).to.equal(0);
+
+      const vestingBalance = await connection.getTokenAccountBalance(vestingTokenAccount!);
+      expect(vestingBalance.value.amount).to.equal(allocation.toString());
+      const curveBalance = await connection.getTokenAccountBalance(tokenAccount);
+      expect(curveBalance.value.amount).to.equal(DEFAULT_TOKEN_SUPPLY.sub(allocation).toString());
+    });
+
+    it("should reject an allocation above the global max", async () => {
+      const creator = Keypair.generate();
+      await airdrop(creator.publicKey, 10 * LAMPORTS_PER_SOL);
+
+      try {
+        await createToken(creator, "Big Vest", "BVST", "https://example.com/bvst.json", null, null, ALLOCATION_MAX_BPS + 1);
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.satisfy(
+          (s: string) => s.includes("InvalidCreatorAllocation") || s.includes("Creator allocation out of bounds")
+        );
+      }
+    });
+
+    it("should not release vested tokens before graduation", async () => {
+      const creator = Keypair.generate();
+      await airdrop(creator.publicKey, 10 * LAMPORTS_PER_SOL);
+
+      const { mint, bondingCurvePda, vesting, vestingTokenAccount } = await createToken(
+        creator, "Early Vest", "EVST", "https://example.com/evst.json", null, null, 500
+      );
+
+      try {
+        await program.methods
+          .claimVested()
+          .accounts({
+            creator: creator.publicKey,
+            mint: mint.publicKey,
+            bondingCurve: bondingCurvePda,
+            vesting: vesting!,
+            vestingTokenAccount: vestingTokenAccount!,
+            creatorTokenAccount: await getAssociatedTokenAddress(mint.publicKey, creator.publicKey),
+            systemProgram: SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          })
+          .signers([creator])
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.satisfy(
+          (s: string) => s.includes("NotGraduated") || s.includes("Bonding curve has not graduated yet")
+        );
+      }
+    });
+  });
+
+  describe("create_token_2022", () => {
+    it("should store the metadata in the Token-2022 mint", async () => {
+      const creator = Keypair.generate();
+      await airdrop(creator.publicKey, 10 * LAMPORTS_PER_SOL);
+
+      const { mint, tokenAccount } = await createToken2022(
+        creator, "Ext Token", "ext", "ipfs://bafyexttoken"
+      );
+
+      const mintInfo = await connection.getAccountInfo(mint.publicKey);
+      expect(mintInfo!.owner.toBase58()).to.equal(TOKEN_2022_PROGRAM_ID.toBase58());
+
+      const metadata = await getTokenMetadata(connection, mint.publicKey, "confirmed", TOKEN_2022_PROGRAM_ID);
+      expect(metadata!.name).to.equal("Ext Token");
+      expect(metadata!.symbol).to.equal("EXT");
+      expect(metadata!.uri).to.equal("ipfs://bafyexttoken");
+      // Immutable by default: update authority revoked at launch
+      expect(metadata!.updateAuthority).to.be.undefined;
+
+      const curveBalance = await connection.getTokenAccountBalance(tokenAccount);
+      expect(curveBalance.value.amount).to.equal(DEFAULT_TOKEN_SUPPLY.toString());
+
+      const mint2022 = await getMint(connection, mint.publicKey, "confirmed", TOKEN_2022_PROGRAM_ID);
+      expect(mint2022.mintAuthority).to.be.null;
+    });
+
+    it("should trade a Token-2022 mint on the curve", async () => {
+      const creator = Keypair.generate();
+      const trader = Keypair.generate();
+      await airdrop(creator.publicKey, 10 * LAMPORTS_PER_SOL);
+      await airdrop(trader.publicKey, 10 * LAMPORTS_PER_SOL);
+
+      const { mint } = await createToken2022(creator, "Ext Trade", "EXTT", "https://example.com/extt.json");
+
+      await buyToken(trader, mint.publicKey, new anchor.BN(LAMPORTS_PER_SOL), new anchor.BN(0), undefined, undefined, TOKEN_2022_PROGRAM_ID);
+
+      const traderAta = await getAssociatedTokenAddress(mint.publicKey, trader.publicKey, false, TOKEN_2022_PROGRAM_ID);
+      const bought = new anchor.BN((await connection.getTokenAccountBalance(traderAta)).value.amount);
+      expect(bought.toNumber()).to.be.greaterThan(0);
+
+      await sellToken(trader, mint.publicKey, bought, new anchor.BN(0), undefined, TOKEN_2022_PROGRAM_ID);
+
+      const after = await connection.getTokenAccountBalance(traderAta);
+      expect(after.value.amount).to.equal("0");
+    });
   });
 
   describe("create_and_buy_token", () => {
@@ -118,7 +362,7 @@ describe("02 - Launch", () => {
       const solAmount = new anchor.BN(1 * LAMPORTS_PER_SOL);
 
       await program.methods
-        .createAndBuyToken("Buy Token", "BUY", "https://example.com/buy.json", solAmount, new anchor.BN(0))
+        .createAndBuyToken("Buy Token", "BUY", "https://example.com/buy.json", solAmount, new anchor.BN(0), null, null, null, null)
         .accounts({
           creator: creator.publicKey,
           global: globalPda,
@@ -128,6 +372,9 @@ describe("02 - Launch", () => {
           feeVault: feeVaultPda,
           tokenAccount: tokenAccount,
           referral: null,
+          walletPurchase: null,
+          vesting: null,
+          vestingTokenAccount: null,
           metadata: metadata,
           systemProgram: SystemProgram.programId,
           tokenProgram: TOKEN_PROGRAM_ID,
@@ -169,7 +416,7 @@ describe("02 - Launch", () => {
 
       try {
         await program.methods
-          .createAndBuyToken("Zero Token", "ZERO", "https://example.com/zero.json", new anchor.BN(0), new anchor.BN(0))
+          .createAndBuyToken("Zero Token", "ZERO", "https://example.com/zero.json", new anchor.BN(0), new anchor.BN(0), null, null, null, null)
           .accounts({
             creator: creator.publicKey,
             global: globalPda,
@@ -179,6 +426,9 @@ describe("02 - Launch", () => {
             feeVault: feeVaultPda,
             tokenAccount: tokenAccount,
             referral: null,
+            walletPurchase: null,
+            vesting: null,
+            vestingTokenAccount: null,
             metadata: metadata,
             systemProgram: SystemProgram.programId,
             tokenProgram: TOKEN_PROGRAM_ID,
@@ -195,5 +445,127 @@ describe("02 - Launch", () => {
         );
       }
     });
+    describe("during the opening window", () => {
+      const globalPda = getGlobalPda();
+
+      before(async () => {
+        // 1 hour window, 1% of supply per wallet
+        await program.methods
+          .updateConfig(configParams({ openingWindow: new anchor.BN(3600), maxWalletBuyBps: 100 }))
+          .accounts({ authority: authority.publicKey, global: globalPda })
+          .rpc();
+      });
+
+      after(async () => {
+        await program.methods
+          .updateConfig(configParams({ openingWindow: new anchor.BN(0) }))
+          .accounts({ authority: authority.publicKey, global: globalPda })
+          .rpc();
+      });
+
+      it("should reject a bundled buy above the per-wallet cap", async () => {
+        const creator = Keypair.generate();
+        await airdrop(creator.publicKey, 10 * LAMPORTS_PER_SOL);
+
+        // ~3.3% of supply
+        try {
+          await createAndBuyToken(creator, new anchor.BN(LAMPORTS_PER_SOL), "Capped Token", "CAP", "https://example.com/cap.json", true);
+          expect.fail("Should have thrown");
+        } catch (err: any) {
+          expect(err.toString()).to.satisfy(
+            (s: string) => s.includes("WalletCapExceeded") || s.includes("Per-wallet buy cap exceeded")
+          );
+        }
+      });
+
+      it("should count the bundled buy toward the creator's cap", async () => {
+        const creator = Keypair.generate();
+        await airdrop(creator.publicKey, 10 * LAMPORTS_PER_SOL);
+
+        // ~0.35% of supply
+        const { mint } = await createAndBuyToken(creator, new anchor.BN(LAMPORTS_PER_SOL / 10), "Window Buy", "WBUY", "https://example.com/wbuy.json", true);
+
+        const walletPurchasePda = getWalletPurchasePda(mint.publicKey, creator.publicKey);
+        const creatorAta = await getAssociatedTokenAddress(mint.publicKey, creator.publicKey);
+        const record = await program.account.walletPurchase.fetch(walletPurchasePda);
+        expect(record.tokensBought.toString()).to.equal((await connection.getTokenAccountBalance(creatorAta)).value.amount);
+
+        // The bundled buy plus a regular one would exceed 1% of supply
+        try {
+          await buyToken(creator, mint.publicKey, new anchor.BN(LAMPORTS_PER_SOL / 4), new anchor.BN(0), undefined, walletPurchasePda);
+          expect.fail("Should have thrown");
+        } catch (err: any) {
+          expect(err.toString()).to.satisfy(
+            (s: string) => s.includes("WalletCapExceeded") || s.includes("Per-wallet buy cap exceeded")
+          );
+        }
+      });
+    });
+  });
+
+  describe("cancel_launch", () => {
+    before(async () => {
+      await allowCreatorAllocations(true);
+    });
+
+    after(async () => {
+      await allowCreatorAllocations(false);
+    });
+
+    const cancelLaunch = async (creator: Keypair, mint: PublicKey, vesting: PublicKey | null = null, vestingTokenAccount: PublicKey | null = null) => {
+      const bondingCurvePda = getBondingCurvePda(mint);
+      await program.methods
+        .cancelLaunch()
+        .accounts({
+          creator: creator.publicKey,
+          mint,
+          bondingCurve: bondingCurvePda,
+          tokenAccount: await getAssociatedTokenAddress(mint, bondingCurvePda, true),
+          vesting,
+          vestingTokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([creator])
+        .rpc();
+    };
+
+    it("should burn the supply and close the curve of an untraded launch", async () => {
+      const creator = Keypair.generate();
+      await airdrop(creator.publicKey, 10 * LAMPORTS_PER_SOL);
+
+      const { mint, bondingCurvePda, tokenAccount, vesting, vestingTokenAccount } = await createToken(
+        creator, "Oops Token", "OOPS", "https://example.com/oops.json", null, null, 500
+      );
+      const balanceBefore = await connection.getBalance(creator.publicKey);
+
+      await cancelLaunch(creator, mint.publicKey, vesting, vestingTokenAccount);
+
+      expect(await program.account.bondingCurve.fetchNullable(bondingCurvePda)).to.be.null;
+      expect(await connection.getAccountInfo(tokenAccount)).to.be.null;
+      expect(await connection.getAccountInfo(vestingTokenAccount!)).to.be.null;
+      const mintInfo = await getMint(connection, mint.publicKey);
+      expect(mintInfo.supply.toString()).to.equal("0");
+      // Rent of the closed accounts came back
+      expect(await connection.getBalance(creator.publicKey)).to.be.greaterThan(balanceBefore);
+    });
+
+    it("should refuse to cancel once someone has bought", async () => {
+      const creator = Keypair.generate();
+      const buyer = Keypair.generate();
+      await airdrop(creator.publicKey, 10 * LAMPORTS_PER_SOL);
+      await airdrop(buyer.publicKey, 10 * LAMPORTS_PER_SOL);
+
+      const { mint } = await createToken(creator, "Live Token", "LIVE", "https://example.com/live.json");
+      await buyToken(buyer, mint.publicKey, new anchor.BN(LAMPORTS_PER_SOL / 10), new anchor.BN(0));
+
+      try {
+        await cancelLaunch(creator, mint.publicKey);
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.satisfy(
+          (s: string) => s.includes("LaunchAlreadyTraded") || s.includes("Launch has already been traded")
+        );
+      }
+    });
   });
 });
@@ -1,5 +1,6 @@
 import * as anchor from "@coral-xyz/anchor";
 import { expect } from "chai";
+import { NATIVE_MINT } from "@solana/spl-token";
 import {
   program,
   provider,
@@ -16,8 +17,11 @@ import {
   ASSOCIATED_TOKEN_PROGRAM_ID,
   getAssociatedTokenAddress,
   DEFAULT_GRADUATION_THRESHOLD,
+  DEFAULT_REAL_TOKENS,
+  configParams,
+  createAndBuyToken,
 } from "./helpers";
-import { getGlobalPda, getBondingCurvePda, getFeeVaultPda } from "./helpers/pda";
+import { getGlobalPda, getBondingCurvePda, getFeeVaultPda, getWalletPurchasePda, getSwapWsolPda } from "./helpers/pda";
 
 describe("03 - Trade", () => {
   const authority = (provider.wallet as anchor.Wallet).payer;
@@ -94,7 +98,7 @@ describe("03 - Trade", () => {
 
       // Pause the program
       await program.methods
-        .updateConfig(null, null, null, null, null, null, null, null, null, { paused: {} })
+        .updateConfig(configParams({ status: { paused: {} } }))
         .accounts({ authority: authority.publicKey, global: globalPda })
         .rpc();
 
@@ -111,7 +115,7 @@ describe("03 - Trade", () => {
       } finally {
         // Restore
         await program.methods
-          .updateConfig(null, null, null, null, null, null, null, null, null, { running: {} })
+          .updateConfig(configParams({ status: { running: {} } }))
           .accounts({ authority: authority.publicKey, global: globalPda })
           .rpc();
       }
@@ -138,6 +142,90 @@ describe("03 - Trade", () => {
     });
   });
 
+  describe("scheduled launch", () => {
+    it("should reject buys before start_time", async () => {
+      const creator = Keypair.generate();
+      await airdrop(creator.publicKey, 10 * LAMPORTS_PER_SOL);
+
+      const startTime = new anchor.BN(Math.floor(Date.now() / 1000) + 3600);
+      const { mint } = await createToken(creator, "Soon Token", "SOON", "https://example.com/soon.json", null, startTime);
+
+      const buyer = Keypair.generate();
+      await airdrop(buyer.publicKey, 5 * LAMPORTS_PER_SOL);
+
+      try {
+        await buyToken(buyer, mint.publicKey, new anchor.BN(LAMPORTS_PER_SOL), new anchor.BN(0));
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.satisfy(
+          (s: string) => s.includes("TradingNotStarted") || s.includes("Trading has not started yet")
+        );
+      }
+    });
+  });
+
+  describe("opening window", () => {
+    const globalPda = getGlobalPda();
+    let mint: Keypair;
+
+    before(async () => {
+      // 1 hour window, 1% of supply per wallet
+      await program.methods
+        .updateConfig(configParams({ openingWindow: new anchor.BN(3600), maxWalletBuyBps: 100 }))
+        .accounts({ authority: authority.publicKey, global: globalPda })
+        .rpc();
+
+      const creator = Keypair.generate();
+      await airdrop(creator.publicKey, 10 * LAMPORTS_PER_SOL);
+      ({ mint } = await createToken(creator, "Window Token", "WIN", "https://example.com/win.json"));
+    });
+
+    after(async () => {
+      await program.methods
+        .updateConfig(configParams({ openingWindow: new anchor.BN(0) }))
+        .accounts({ authority: authority.publicKey, global: globalPda })
+        .rpc();
+    });
+
+    it("should require the wallet purchase account during the window", async () => {
+      const buyer = Keypair.generate();
+      await airdrop(buyer.publicKey, 5 * LAMPORTS_PER_SOL);
+
+      try {
+        await buyToken(buyer, mint.publicKey, new anchor.BN(LAMPORTS_PER_SOL / 10), new anchor.BN(0));
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.satisfy(
+          (s: string) => s.includes("WalletPurchaseRequired") || s.includes("Wallet purchase account required")
+        );
+      }
+    });
+
+    it("should track buys and reject those above the per-wallet cap", async () => {
+      const buyer = Keypair.generate();
+      await airdrop(buyer.publicKey, 10 * LAMPORTS_PER_SOL);
+      const walletPurchasePda = getWalletPurchasePda(mint.publicKey, buyer.publicKey);
+
+      // ~0.35% of supply
+      await buyToken(buyer, mint.publicKey, new anchor.BN(LAMPORTS_PER_SOL / 10), new anchor.BN(0), undefined, walletPurchasePda);
+
+      const buyerAta = await getAssociatedTokenAddress(mint.publicKey, buyer.publicKey);
+      const balance = (await connection.getTokenAccountBalance(buyerAta)).value.amount;
+      const record = await program.account.walletPurchase.fetch(walletPurchasePda);
+      expect(record.tokensBought.toString()).to.equal(balance);
+
+      // Cumulative buys would exceed 1% of supply
+      try {
+        await buyToken(buyer, mint.publicKey, new anchor.BN(LAMPORTS_PER_SOL / 2), new anchor.BN(0), undefined, walletPurchasePda);
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.satisfy(
+          (s: string) => s.includes("WalletCapExceeded") || s.includes("Per-wallet buy cap exceeded")
+        );
+      }
+    });
+  });
+
   describe("sell_token", () => {
     let seller: Keypair;
     let sellerTokens: anchor.BN;
@@ -185,7 +273,7 @@ describe("03 - Trade", () => {
       const globalPda = getGlobalPda();
 
       await program.methods
-        .updateConfig(null, null, null, null, null, null, null, null, null, { paused: {} })
+        .updateConfig(configParams({ status: { paused: {} } }))
         .accounts({ authority: authority.publicKey, global: globalPda })
         .rpc();
 
@@ -198,7 +286,7 @@ describe("03 - Trade", () => {
         );
       } finally {
         await program.methods
-          .updateConfig(null, null, null, null, null, null, null, null, null, { running: {} })
+          .updateConfig(configParams({ status: { running: {} } }))
           .accounts({ authority: authority.publicKey, global: globalPda })
           .rpc();
       }
@@ -260,6 +348,145 @@ describe("03 - Trade", () => {
     });
   });
 
+  describe("swap", () => {
+    let trader: Keypair;
+
+    const swap = async (isBuy: boolean, amountIn: anchor.BN, minAmountOut: anchor.BN) => {
+      const bc = await program.account.bondingCurve.fetch(testBondingCurvePda);
+      await program.methods
+        .swap(isBuy, amountIn, minAmountOut)
+        .accounts({
+          trader: trader.publicKey,
+          mint: testMint.publicKey,
+          bondingCurve: testBondingCurvePda,
+          graduationRecord: null,
+          tokenAccount: testTokenAccount,
+          creatorAccount: bc.creator,
+          referral: null,
+          walletPurchase: null,
+          cpSwapProgram: null,
+          authorityRaydium: null,
+          ammConfig: null,
+          poolState: null,
+          tokenVault: null,
+          wsolVault: null,
+          observationState: null,
+          wsolMint: null,
+          traderWsolAccount: null,
+          wsolTokenProgram: null,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        } as any)
+        .signers([trader])
+        .rpc();
+    };
+
+    before(async () => {
+      trader = Keypair.generate();
+      await airdrop(trader.publicKey, 10 * LAMPORTS_PER_SOL);
+    });
+
+    it("should buy on the live curve like buy_token", async () => {
+      const bcBefore = await program.account.bondingCurve.fetch(testBondingCurvePda);
+      const solAmount = new anchor.BN(LAMPORTS_PER_SOL);
+
+      await swap(true, solAmount, new anchor.BN(0));
+
+      const bcAfter = await program.account.bondingCurve.fetch(testBondingCurvePda);
+      const solAfterFee = solAmount.sub(solAmount.muln(100).divn(10_000));
+      expect(bcAfter.realSolReserves.toString()).to.equal(bcBefore.realSolReserves.add(solAfterFee).toString());
+
+      const traderAta = await getAssociatedTokenAddress(testMint.publicKey, trader.publicKey);
+      const balance = await connection.getTokenAccountBalance(traderAta);
+      expect(Number(balance.value.amount)).to.be.greaterThan(0);
+    });
+
+    it("should sell on the live curve like sell_token", async () => {
+      const traderAta = await getAssociatedTokenAddress(testMint.publicKey, trader.publicKey);
+      const tokens = new anchor.BN((await connection.getTokenAccountBalance(traderAta)).value.amount);
+      const bcBefore = await program.account.bondingCurve.fetch(testBondingCurvePda);
+
+      await swap(false, tokens, new anchor.BN(0));
+
+      const bcAfter = await program.account.bondingCurve.fetch(testBondingCurvePda);
+      expect(bcAfter.realSolReserves.lt(bcBefore.realSolReserves)).to.be.true;
+      expect((await connection.getTokenAccountBalance(traderAta)).value.amount).to.equal("0");
+    });
+
+    it("should reject a live curve swap without the curve vault", async () => {
+      const bc = await program.account.bondingCurve.fetch(testBondingCurvePda);
+      try {
+        await program.methods
+          .swap(true, new anchor.BN(LAMPORTS_PER_SOL / 10), new anchor.BN(0))
+          .accounts({
+            trader: trader.publicKey,
+            mint: testMint.publicKey,
+            bondingCurve: testBondingCurvePda,
+            graduationRecord: null,
+            tokenAccount: null,
+            creatorAccount: bc.creator,
+            referral: null,
+            walletPurchase: null,
+            cpSwapProgram: null,
+            authorityRaydium: null,
+            ammConfig: null,
+            poolState: null,
+            tokenVault: null,
+            wsolVault: null,
+            observationState: null,
+            wsolMint: null,
+            traderWsolAccount: null,
+            wsolTokenProgram: null,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          } as any)
+          .signers([trader])
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.satisfy(
+          (s: string) => s.includes("SwapAccountsRequired") || s.includes("Accounts for this swap route are missing")
+        );
+      }
+    });
+
+    it("should reject the transit wSOL account on a live curve swap", async () => {
+      const bc = await program.account.bondingCurve.fetch(testBondingCurvePda);
+      try {
+        await program.methods
+          .swap(true, new anchor.BN(LAMPORTS_PER_SOL / 10), new anchor.BN(0))
+          .accounts({
+            trader: trader.publicKey,
+            mint: testMint.publicKey,
+            bondingCurve: testBondingCurvePda,
+            graduationRecord: null,
+            tokenAccount: testTokenAccount,
+            creatorAccount: bc.creator,
+            referral: null,
+            walletPurchase: null,
+            cpSwapProgram: null,
+            authorityRaydium: null,
+            ammConfig: null,
+            poolState: null,
+            tokenVault: null,
+            wsolVault: null,
+            observationState: null,
+            wsolMint: NATIVE_MINT,
+            traderWsolAccount: getSwapWsolPda(trader.publicKey),
+            wsolTokenProgram: TOKEN_PROGRAM_ID,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          } as any)
+          .signers([trader])
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.satisfy(
+          (s: string) => s.includes("UnexpectedWsolAccount") || s.includes("Transit wSOL account only allowed on a Raydium swap")
+        );
+      }
+      // the failed swap rolled back its creation
+      expect(await connection.getAccountInfo(getSwapWsolPda(trader.publicKey))).to.be.null;
+    });
+  });
+
   describe("graduation", () => {
     it("should mark curve as completed when threshold is reached", async () => {
       const globalPda = getGlobalPda();
@@ -267,7 +494,7 @@ describe("03 - Trade", () => {
       // Set a very low graduation threshold (2 SOL)
       const lowThreshold = new anchor.BN(2 * LAMPORTS_PER_SOL);
       await program.methods
-        .updateConfig(null, null, null, null, null, null, null, null, lowThreshold, null)
+        .updateConfig(configParams({ graduationThreshold: lowThreshold }))
         .accounts({ authority: authority.publicKey, global: globalPda })
         .rpc();
 
@@ -292,7 +519,7 @@ describe("03 - Trade", () => {
 
       // Restore threshold
       await program.methods
-        .updateConfig(null, null, null, null, null, null, null, null, DEFAULT_GRADUATION_THRESHOLD, null)
+        .updateConfig(configParams({ graduationThreshold: DEFAULT_GRADUATION_THRESHOLD }))
         .accounts({ authority: authority.publicKey, global: globalPda })
         .rpc();
     });
@@ -301,7 +528,7 @@ describe("03 - Trade", () => {
       const globalPda = getGlobalPda();
       const lowThreshold = new anchor.BN(2 * LAMPORTS_PER_SOL);
       await program.methods
-        .updateConfig(null, null, null, null, null, null, null, null, lowThreshold, null)
+        .updateConfig(configParams({ graduationThreshold: lowThreshold }))
         .accounts({ authority: authority.publicKey, global: globalPda })
         .rpc();
 
@@ -339,7 +566,7 @@ describe("03 - Trade", () => {
 
       // Restore threshold
       await program.methods
-        .updateConfig(null, null, null, null, null, null, null, null, DEFAULT_GRADUATION_THRESHOLD, null)
+        .updateConfig(configParams({ graduationThreshold: DEFAULT_GRADUATION_THRESHOLD }))
         .accounts({ authority: authority.publicKey, global: globalPda })
         .rpc();
     });
@@ -348,7 +575,7 @@ describe("03 - Trade", () => {
       const globalPda = getGlobalPda();
       const lowThreshold = new anchor.BN(2 * LAMPORTS_PER_SOL);
       await program.methods
-        .updateConfig(null, null, null, null, null, null, null, null, lowThreshold, null)
+        .updateConfig(configParams({ graduationThreshold: lowThreshold }))
         .accounts({ authority: authority.publicKey, global: globalPda })
         .rpc();
 
@@ -386,9 +613,41 @@ describe("03 - Trade", () => {
 
       // Restore threshold
       await program.methods
-        .updateConfig(null, null, null, null, null, null, null, null, DEFAULT_GRADUATION_THRESHOLD, null)
+        .updateConfig(configParams({ graduationThreshold: DEFAULT_GRADUATION_THRESHOLD }))
+        .accounts({ authority: authority.publicKey, global: globalPda })
+        .rpc();
+    });
+
+    it("should clip the last buy to the remaining tokens in tokens-sold mode", async () => {
+      const globalPda = getGlobalPda();
+      await program.methods
+        .updateConfig(configParams({ graduationMode: { tokensSold: {} } }))
         .accounts({ authority: authority.publicKey, global: globalPda })
         .rpc();
+
+      try {
+        const creator = Keypair.generate();
+        await airdrop(creator.publicKey, 120 * LAMPORTS_PER_SOL);
+
+        // ~85 SOL buys the whole real_token, the creator's bundled buy sends more
+        const { mint, bondingCurvePda } = await createAndBuyToken(creator, new anchor.BN(100 * LAMPORTS_PER_SOL), "Sold Out", "SOLD", "https://example.com/sold.json");
+
+        const bc = await program.account.bondingCurve.fetch(bondingCurvePda);
+        expect(bc.realToken.toNumber()).to.equal(0);
+        expect(bc.completed).to.be.true;
+
+        const creatorAta = await getAssociatedTokenAddress(mint.publicKey, creator.publicKey);
+        expect((await connection.getTokenAccountBalance(creatorAta)).value.amount).to.equal(DEFAULT_REAL_TOKENS.toString());
+
+        // Only the price of the remaining tokens is charged: the curve never gets less than that
+        expect(bc.realSolReserves.gte(new anchor.BN(85 * LAMPORTS_PER_SOL))).to.be.true;
+        expect(bc.realSolReserves.lt(new anchor.BN(86 * LAMPORTS_PER_SOL))).to.be.true;
+      } finally {
+        await program.methods
+          .updateConfig(configParams({ graduationMode: { realSol: {} } }))
+          .accounts({ authority: authority.publicKey, global: globalPda })
+          .rpc();
+      }
     });
   });
 });
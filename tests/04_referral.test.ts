@@ -14,6 +14,8 @@ import {
   TOKEN_PROGRAM_ID,
   ASSOCIATED_TOKEN_PROGRAM_ID,
   getAssociatedTokenAddress,
+  DEFAULT_CREATION_FEE,
+  configParams,
 } from "./helpers";
 import { getGlobalPda, getBondingCurvePda, getFeeVaultPda, getReferralPda } from "./helpers/pda";
 
@@ -117,15 +119,13 @@ describe("04 - Referral", () => {
       const referralBalanceAfter = await connection.getBalance(referralPda);
 
       // Total fee = 1% of 1 SOL = 0.01 SOL = 10_000_000 lamports
-      // Creator share = 30% of fee (goes to token creator, not fee_vault)
-      // Remaining = 70% of fee
-      // Referral share = 10% of remaining
-      // Protocol share = remaining - referral
+      // Creator share = 65% of fee (goes to token creator, not fee_vault)
+      // Protocol share = fee - creator share
+      // Referral share = 10% of fee, carved out of the creator share
       const totalFee = solAmount.toNumber() * 100 / 10000;
       const creatorFee = Math.floor(totalFee * 6500 / 10000); // creator_share_bps = 6500
-      const remainingFee = totalFee - creatorFee;
-      const expectedReferralFee = Math.floor(remainingFee * 1000 / 10000); // referral_share_bps = 1000
-      const expectedProtocolFee = remainingFee - expectedReferralFee;
+      const expectedProtocolFee = totalFee - creatorFee;
+      const expectedReferralFee = Math.floor(totalFee * 1000 / 10000); // curve referral_share_bps = 1000
 
       const feeVaultDelta = feeVaultAfter - feeVaultBefore;
       const referralDelta = referralBalanceAfter - referralBalanceBefore;
@@ -138,6 +138,49 @@ describe("04 - Referral", () => {
       expect(referralAccount.totalEarned.toNumber()).to.equal(referralDelta);
       expect(referralAccount.tradeCount.toNumber()).to.equal(1);
     });
+
+    it("should pay the curve's custom referral share out of the creator share", async () => {
+      const referrer = Keypair.generate();
+      await airdrop(referrer.publicKey, 5 * LAMPORTS_PER_SOL);
+
+      const referralPda = getReferralPda(referrer.publicKey);
+      await program.methods
+        .registerReferral()
+        .accounts({
+          user: referrer.publicKey,
+          referral: referralPda,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([referrer])
+        .rpc();
+
+      // Creator pays referrers 30% of the fee instead of the default 10%
+      const creator = Keypair.generate();
+      await airdrop(creator.publicKey, 10 * LAMPORTS_PER_SOL);
+      const { mint, bondingCurvePda } = await createToken(creator, "Ref Token", "REF", "https://example.com/ref.json", 3000);
+
+      const bc = await program.account.bondingCurve.fetch(bondingCurvePda);
+      expect(bc.referralShareBps).to.equal(3000);
+
+      const feeVaultPda = getFeeVaultPda();
+      const feeVaultBefore = await connection.getBalance(feeVaultPda);
+      const referralBalanceBefore = await connection.getBalance(referralPda);
+      const creatorBalanceBefore = await connection.getBalance(creator.publicKey);
+
+      const buyer = Keypair.generate();
+      await airdrop(buyer.publicKey, 10 * LAMPORTS_PER_SOL);
+
+      const solAmount = new anchor.BN(1 * LAMPORTS_PER_SOL);
+      await buyToken(buyer, mint.publicKey, solAmount, new anchor.BN(0), referralPda);
+
+      const totalFee = solAmount.toNumber() * 100 / 10000;
+      const creatorShare = Math.floor(totalFee * 6500 / 10000);
+      const expectedReferralFee = Math.floor(totalFee * 3000 / 10000);
+
+      expect(await connection.getBalance(feeVaultPda) - feeVaultBefore).to.equal(totalFee - creatorShare);
+      expect(await connection.getBalance(referralPda) - referralBalanceBefore).to.equal(expectedReferralFee);
+      expect(await connection.getBalance(creator.publicKey) - creatorBalanceBefore).to.equal(creatorShare - expectedReferralFee);
+    });
   });
 
   describe("claim_referral_fees", () => {
@@ -226,4 +269,65 @@ describe("04 - Referral", () => {
       }
     });
   });
+
+  describe("creation fee", () => {
+    const globalPda = getGlobalPda();
+    const feeVaultPda = getFeeVaultPda();
+
+    const setCreationReferralShare = (bps: number) =>
+      program.methods
+        .updateConfig(configParams({ creationReferralShareBps: bps }))
+        .accounts({ authority: authority.publicKey, global: globalPda })
+        .rpc();
+
+    it("should charge the creation fee into the fee vault", async () => {
+      const creator = Keypair.generate();
+      await airdrop(creator.publicKey, 10 * LAMPORTS_PER_SOL);
+
+      const vaultBefore = await connection.getBalance(feeVaultPda);
+      await createToken(creator, "Fee Token", "FEE", "https://example.com/fee.json");
+      const vaultAfter = await connection.getBalance(feeVaultPda);
+
+      expect(vaultAfter - vaultBefore).to.equal(DEFAULT_CREATION_FEE.toNumber());
+    });
+
+    it("should pay the referrer its share of the creation fee", async () => {
+      const referrer = Keypair.generate();
+      const creator = Keypair.generate();
+      await airdrop(referrer.publicKey, 2 * LAMPORTS_PER_SOL);
+      await airdrop(creator.publicKey, 10 * LAMPORTS_PER_SOL);
+
+      const referralPda = getReferralPda(referrer.publicKey);
+      await program.methods
+        .registerReferral()
+        .accounts({
+          user: referrer.publicKey,
+          referral: referralPda,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([referrer])
+        .rpc();
+
+      await setCreationReferralShare(2000); // 20% of the creation fee
+      try {
+        const vaultBefore = await connection.getBalance(feeVaultPda);
+        const referralBefore = await connection.getBalance(referralPda);
+
+        const { bondingCurvePda } = await createToken(creator, "Ref Launch", "RLNC", "https://example.com/rlnc.json", null, null, null, referralPda);
+
+        const referralShare = DEFAULT_CREATION_FEE.muln(2000).divn(10000).toNumber();
+        expect((await connection.getBalance(referralPda)) - referralBefore).to.equal(referralShare);
+        expect((await connection.getBalance(feeVaultPda)) - vaultBefore).to.equal(DEFAULT_CREATION_FEE.toNumber() - referralShare);
+
+        const referralAccount = await program.account.referral.fetch(referralPda);
+        expect(referralAccount.totalEarned.toNumber()).to.equal(referralShare);
+
+        // Kept on the curve for the locked LP fee split
+        const bc = await program.account.bondingCurve.fetch(bondingCurvePda);
+        expect(bc.launchReferrer!.toBase58()).to.equal(referrer.publicKey.toBase58());
+      } finally {
+        await setCreationReferralShare(0);
+      }
+    });
+  });
 });
@@ -0,0 +1,535 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import { ComputeBudgetProgram } from "@solana/web3.js";
+import { NATIVE_MINT, createAssociatedTokenAccountIdempotent } from "@solana/spl-token";
+import {
+  program,
+  provider,
+  connection,
+  airdrop,
+  createToken,
+  buyToken,
+  migrateToRaydium,
+  buyAndMigrate,
+  createAndBuyToken2022,
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  LAMPORTS_PER_SOL,
+  TOKEN_PROGRAM_ID,
+  TOKEN_2022_PROGRAM_ID,
+  CPMM_PROGRAM_ID,
+  CPMM_AMM_CONFIG,
+  MEMO_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  getAssociatedTokenAddress,
+  DEFAULT_MIGRATION_FEE,
+  DEFAULT_VIRTUAL_SOL,
+  configParams,
+  allowCreatorAllocations,
+  ALLOCATION_MAX_BPS,
+} from "./helpers";
+import {
+  getGlobalPda,
+  getBondingCurvePda,
+  getFeeVaultPda,
+  getGraduationRecordPda,
+  getMigrationTokenPda,
+  getMigrationWsolPda,
+  getSwapWsolPda,
+  getCpmmPool,
+  getLpLockPda,
+  getReferralPda,
+} from "./helpers/pda";
+
+describe("05 - Migration", () => {
+  const authority = (provider.wallet as anchor.Wallet).payer;
+  const globalPda = getGlobalPda();
+  const feeVaultPda = getFeeVaultPda();
+  const creator = Keypair.generate();
+  const buyer = Keypair.generate();
+  const cranker = Keypair.generate();
+
+  const setGraduationMode = (graduationMode: any) =>
+    program.methods
+      .updateConfig(configParams({ graduationMode }))
+      .accounts({ authority: authority.publicKey, global: globalPda })
+      .rpc();
+
+  const finalizeMigration = async (mint: PublicKey, graduationRecord: PublicKey | null) => {
+    const bondingCurvePda = getBondingCurvePda(mint);
+    const bc = await program.account.bondingCurve.fetch(bondingCurvePda);
+    const accounts: any = {
+      caller: cranker.publicKey,
+      global: globalPda,
+      mint,
+      bondingCurve: bondingCurvePda,
+      tokenAccount: await getAssociatedTokenAddress(mint, bondingCurvePda, true),
+      graduationRecord,
+      vesting: null,
+      feeVault: feeVaultPda,
+      creatorAccount: bc.creator,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    };
+    await program.methods
+      .finalizeMigration()
+      .accounts(accounts)
+      .signers([cranker])
+      .rpc();
+  };
+
+  const setKeepGraduationRecord = (keepGraduationRecord: boolean) =>
+    program.methods
+      .updateConfig(configParams({ keepGraduationRecord }))
+      .accounts({ authority: authority.publicKey, global: globalPda })
+      .rpc();
+
+  // Raydium leg of `swap` for a migrated mint, the cranker trading
+  const swapOnPool = async (
+    mint: PublicKey,
+    bondingCurve: PublicKey | null,
+    graduationRecord: PublicKey | null,
+    creatorAccount: PublicKey,
+    solAmount: anchor.BN,
+    tokenProgram: PublicKey = TOKEN_PROGRAM_ID
+  ) => {
+    const pool = getCpmmPool(mint);
+    const accounts: any = {
+      trader: cranker.publicKey,
+      mint,
+      bondingCurve,
+      graduationRecord,
+      traderTokenAccount: await getAssociatedTokenAddress(mint, cranker.publicKey, false, tokenProgram),
+      tokenAccount: null,
+      creatorAccount,
+      referral: null,
+      walletPurchase: null,
+      cpSwapProgram: CPMM_PROGRAM_ID,
+      authorityRaydium: pool.authority,
+      ammConfig: CPMM_AMM_CONFIG,
+      poolState: pool.poolState,
+      tokenVault: pool.tokenVault,
+      wsolVault: pool.wsolVault,
+      observationState: pool.observationState,
+      wsolMint: NATIVE_MINT,
+      traderWsolAccount: getSwapWsolPda(cranker.publicKey),
+      wsolTokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+      tokenProgram,
+    };
+    await program.methods
+      .swap(true, solAmount, new anchor.BN(0))
+      .accounts(accounts)
+      .signers([cranker])
+      .rpc();
+  };
+
+  const tokenBalance = async (account: PublicKey) =>
+    new anchor.BN((await connection.getTokenAccountBalance(account)).value.amount);
+
+  // Tokens-sold mode clips the last buy to the curve's remaining tokens: one buy graduates it
+  const graduate = async (name: string, symbol: string, creatorAllocationBps: number | null = null) => {
+    const launched = await createToken(
+      creator, name, symbol, `https://example.com/${symbol.toLowerCase()}.json`, null, null, creatorAllocationBps
+    );
+    await buyToken(buyer, launched.mint.publicKey, new anchor.BN(100 * LAMPORTS_PER_SOL), new anchor.BN(0));
+    expect((await program.account.bondingCurve.fetch(launched.bondingCurvePda)).completed).to.be.true;
+    return launched;
+  };
+
+  before(async () => {
+    await airdrop(creator.publicKey, 10 * LAMPORTS_PER_SOL);
+    // ~86 SOL per graduation
+    for (let i = 0; i < 6; i++) {
+      await airdrop(buyer.publicKey, 100 * LAMPORTS_PER_SOL);
+    }
+    await airdrop(cranker.publicKey, 2 * LAMPORTS_PER_SOL);
+    await setGraduationMode({ tokensSold: {} });
+  });
+
+  after(async () => {
+    await setGraduationMode({ realSol: {} });
+  });
+
+  let migratedMint: PublicKey;
+
+  it("should migrate at the curve price and pay the caller only the crank reward", async () => {
+    const { mint, bondingCurvePda, tokenAccount } = await graduate("Pool Party", "POOL");
+    const crankerBefore = await connection.getBalance(cranker.publicKey);
+
+    const pool = await migrateToRaydium(cranker, mint.publicKey);
+    migratedMint = mint.publicKey;
+
+    const bc = await program.account.bondingCurve.fetch(bondingCurvePda);
+    expect(bc.migrated).to.be.true;
+    expect(bc.pool.toBase58()).to.equal(pool.poolState.toBase58());
+    expect((await tokenBalance(tokenAccount)).toString()).to.equal("0");
+
+    // the pool holds exactly the recorded deposit, at the curve's last price (1% tolerance)
+    expect((await tokenBalance(pool.tokenVault)).toString()).to.equal(bc.poolTokenAmount.toString());
+    expect((await tokenBalance(pool.wsolVault)).toString()).to.equal(bc.poolSolAmount.toString());
+    const poolSide = bc.poolSolAmount.mul(bc.virtualToken);
+    const curveSide = bc.poolTokenAmount.mul(bc.virtualSol);
+    expect(poolSide.sub(curveSide).abs().muln(10_000).lte(curveSide.muln(100))).to.be.true;
+
+    // pool creation is paid back by the fee vault, transit and LP accounts are closed
+    expect(await connection.getBalance(cranker.publicKey)).to.be.greaterThan(crankerBefore);
+    expect(await connection.getAccountInfo(getMigrationTokenPda(mint.publicKey))).to.be.null;
+    expect(await connection.getAccountInfo(getMigrationWsolPda(mint.publicKey))).to.be.null;
+    expect(await connection.getAccountInfo(await getAssociatedTokenAddress(pool.lpMint, cranker.publicKey))).to.be.null;
+  });
+
+  it("should migrate a curve with a creator allocation, all of its SOL in the pool", async () => {
+    // the allocation curve sells fewer tokens, so its vault pairs the reserves with the allocation carved out
+    await allowCreatorAllocations(true);
+    try {
+      const { mint, bondingCurvePda, tokenAccount } = await graduate("Vested Pool", "VPOOL", ALLOCATION_MAX_BPS);
+      const vaultTokens = await tokenBalance(tokenAccount);
+
+      const pool = await migrateToRaydium(cranker, mint.publicKey);
+
+      const bc = await program.account.bondingCurve.fetch(bondingCurvePda);
+      expect(bc.migrated).to.be.true;
+
+      // every lamport left after the migration fee goes in, the vault keeps a leftover
+      expect(bc.poolSolAmount.toString()).to.equal(bc.realSolReserves.sub(DEFAULT_MIGRATION_FEE).toString());
+      expect(bc.poolTokenAmount.lt(vaultTokens)).to.be.true;
+      expect((await tokenBalance(tokenAccount)).toString()).to.equal("0");
+      expect((await tokenBalance(pool.tokenVault)).toString()).to.equal(bc.poolTokenAmount.toString());
+      expect((await tokenBalance(pool.wsolVault)).toString()).to.equal(bc.poolSolAmount.toString());
+
+      const poolSide = bc.poolSolAmount.mul(bc.virtualToken);
+      const curveSide = bc.poolTokenAmount.mul(bc.virtualSol);
+      expect(poolSide.sub(curveSide).abs().muln(10_000).lte(curveSide.muln(100))).to.be.true;
+    } finally {
+      await allowCreatorAllocations(false);
+    }
+  });
+
+  it("should release the allocation of a migrated curve after the cliff, linearly", async () => {
+    // cliff and duration are copied to the vesting at launch
+    const setVesting = (cliff: number, duration: number) =>
+      program.methods
+        .updateConfig(configParams({ vestingCliff: new anchor.BN(cliff), vestingDuration: new anchor.BN(duration) }))
+        .accounts({ authority: authority.publicKey, global: globalPda })
+        .rpc();
+    const claimVested = async (mint: PublicKey, vesting: PublicKey, vestingTokenAccount: PublicKey) =>
+      program.methods
+        .claimVested()
+        .accounts({
+          creator: creator.publicKey,
+          mint,
+          bondingCurve: getBondingCurvePda(mint),
+          vesting,
+          vestingTokenAccount,
+          creatorTokenAccount: await getAssociatedTokenAddress(mint, creator.publicKey),
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        })
+        .signers([creator])
+        .rpc();
+
+    await allowCreatorAllocations(true);
+    await setVesting(10, 3600);
+    const launched = await graduate("Vesting Pool", "VESTP", ALLOCATION_MAX_BPS).finally(async () => {
+      await setVesting(30 * 24 * 60 * 60, 180 * 24 * 60 * 60);
+      await allowCreatorAllocations(false);
+    });
+    const mint = launched.mint.publicKey;
+    const vesting = launched.vesting!;
+    const vestingTokenAccount = launched.vestingTokenAccount!;
+    await migrateToRaydium(cranker, mint);
+
+    // within the cliff nothing is released yet
+    try {
+      await claimVested(mint, vesting, vestingTokenAccount);
+      expect.fail("Should have thrown");
+    } catch (err: any) {
+      expect(err.toString()).to.satisfy(
+        (s: string) => s.includes("NothingToClaim") || s.includes("Nothing to claim")
+      );
+    }
+
+    await new Promise((resolve) => setTimeout(resolve, 11_000));
+    await claimVested(mint, vesting, vestingTokenAccount);
+
+    // past the cliff, a slice of the hour-long linear release
+    const vested = await program.account.creatorVesting.fetch(vesting);
+    expect(vested.claimedAmount.gtn(0)).to.be.true;
+    expect(vested.claimedAmount.lt(vested.totalAmount.divn(10))).to.be.true;
+    const claimed = await tokenBalance(await getAssociatedTokenAddress(mint, creator.publicKey));
+    expect(claimed.toString()).to.equal(vested.claimedAmount.toString());
+    expect((await tokenBalance(vestingTokenAccount)).toString()).to.equal(vested.totalAmount.sub(claimed).toString());
+  });
+
+  it("should refuse to migrate when the reserves can't cover the migration fee", async () => {
+    const { mint, bondingCurvePda } = await graduate("Costly Pool", "COST");
+    const setMigrationFee = (lamports: anchor.BN, initialVirtualSolReserves: anchor.BN) =>
+      program.methods
+        .updateConfig(configParams({ migrationFee: { fixed: { lamports } }, initialVirtualSolReserves }))
+        .accounts({ authority: authority.publicKey, global: globalPda })
+        .rpc();
+
+    // tokens-sold mode graduated the curve at ~85 SOL, below this fee; a deeper curve
+    // would graduate with ~850 SOL, so update_config accepts the fee
+    await setMigrationFee(new anchor.BN(90 * LAMPORTS_PER_SOL), new anchor.BN(300 * LAMPORTS_PER_SOL));
+    try {
+      await migrateToRaydium(cranker, mint.publicKey);
+      expect.fail("Should have thrown");
+    } catch (err: any) {
+      expect(err.toString()).to.satisfy(
+        (s: string) => s.includes("ReservesBelowMigrationFee") || s.includes("Reserves too small to pay the migration fee")
+      );
+    } finally {
+      await setMigrationFee(DEFAULT_MIGRATION_FEE, DEFAULT_VIRTUAL_SOL);
+    }
+    expect((await program.account.bondingCurve.fetch(bondingCurvePda)).migrated).to.be.false;
+  });
+
+  it("should buy the rest of the curve and migrate it in one transaction", async () => {
+    const { mint, bondingCurvePda, tokenAccount } = await createToken(
+      creator, "Last Buy", "LAST", "https://example.com/last.json"
+    );
+
+    const pool = await buyAndMigrate(buyer, mint.publicKey, new anchor.BN(100 * LAMPORTS_PER_SOL), new anchor.BN(0));
+
+    // the buy and the migration wrote the same curve: completed, migrated, vault emptied into the pool
+    const bc = await program.account.bondingCurve.fetch(bondingCurvePda);
+    expect(bc.completed).to.be.true;
+    expect(bc.migrated).to.be.true;
+    expect(bc.pool.toBase58()).to.equal(pool.poolState.toBase58());
+    expect((await tokenBalance(tokenAccount)).toString()).to.equal("0");
+    expect((await tokenBalance(pool.tokenVault)).toString()).to.equal(bc.poolTokenAmount.toString());
+    expect((await tokenBalance(pool.wsolVault)).toString()).to.equal(bc.poolSolAmount.toString());
+
+    const buyerTokens = await tokenBalance(await getAssociatedTokenAddress(mint.publicKey, buyer.publicKey));
+    expect(buyerTokens.gtn(0)).to.be.true;
+  });
+
+  it("should migrate a Token-2022 launch and swap on its pool", async () => {
+    // the creator's bundled buy graduates the curve
+    const { mint, bondingCurvePda, tokenAccount } = await createAndBuyToken2022(
+      buyer, new anchor.BN(100 * LAMPORTS_PER_SOL), "Ext Pool", "EXTP", "https://example.com/extp.json"
+    );
+    expect((await program.account.bondingCurve.fetch(bondingCurvePda)).completed).to.be.true;
+
+    const pool = await migrateToRaydium(cranker, mint.publicKey, TOKEN_2022_PROGRAM_ID);
+
+    const bc = await program.account.bondingCurve.fetch(bondingCurvePda);
+    expect(bc.migrated).to.be.true;
+    expect((await tokenBalance(tokenAccount)).toString()).to.equal("0");
+    expect((await tokenBalance(pool.tokenVault)).toString()).to.equal(bc.poolTokenAmount.toString());
+    expect((await tokenBalance(pool.wsolVault)).toString()).to.equal(bc.poolSolAmount.toString());
+
+    const traderAta = await getAssociatedTokenAddress(mint.publicKey, cranker.publicKey, false, TOKEN_2022_PROGRAM_ID);
+    await swapOnPool(mint.publicKey, bondingCurvePda, null, bc.creator, new anchor.BN(LAMPORTS_PER_SOL / 10), TOKEN_2022_PROGRAM_ID);
+
+    // the pool traded through token_interface: tokens out of the Token-2022 vault, SOL in
+    expect((await tokenBalance(traderAta)).gtn(0)).to.be.true;
+    expect((await tokenBalance(pool.wsolVault)).gt(bc.poolSolAmount)).to.be.true;
+    expect(await connection.getAccountInfo(getSwapWsolPda(cranker.publicKey))).to.be.null;
+  });
+
+  describe("finalize_migration without a graduation record", () => {
+    before(async () => {
+      await setKeepGraduationRecord(false);
+    });
+
+    after(async () => {
+      await setKeepGraduationRecord(true);
+    });
+
+    it("should reject a graduation record the config doesn't keep", async () => {
+      try {
+        await finalizeMigration(migratedMint, getGraduationRecordPda(migratedMint));
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.satisfy(
+          (s: string) => s.includes("GraduationRecordNotKept") || s.includes("Graduation record not kept by the config")
+        );
+      }
+    });
+
+    it("should close the curve and leave no record", async () => {
+      await finalizeMigration(migratedMint, null);
+
+      expect(await connection.getAccountInfo(getBondingCurvePda(migratedMint))).to.be.null;
+      expect(await connection.getAccountInfo(getGraduationRecordPda(migratedMint))).to.be.null;
+    });
+
+    it("should swap a finalized mint with no record on its canonical pool, the creator share to the protocol", async () => {
+      const pool = getCpmmPool(migratedMint);
+      const wsolBefore = await tokenBalance(pool.wsolVault);
+      const creatorBefore = await connection.getBalance(creator.publicKey);
+      const feeVaultBefore = await connection.getBalance(feeVaultPda);
+      const solAmount = new anchor.BN(LAMPORTS_PER_SOL / 10);
+
+      await swapOnPool(migratedMint, null, null, creator.publicKey, solAmount);
+
+      const traderAta = await getAssociatedTokenAddress(migratedMint, cranker.publicKey);
+      expect((await tokenBalance(traderAta)).gtn(0)).to.be.true;
+      expect((await tokenBalance(pool.wsolVault)).gt(wsolBefore)).to.be.true;
+      expect(await connection.getBalance(creator.publicKey)).to.equal(creatorBefore);
+      const fee = solAmount.muln(100).divn(10_000).toNumber();
+      expect(await connection.getBalance(feeVaultPda)).to.equal(feeVaultBefore + fee);
+    });
+  });
+
+  describe("LP lock", () => {
+    const referrer = Keypair.generate();
+    const newCreator = Keypair.generate();
+    let lockedMint: PublicKey;
+
+    const setLpMode = (lpMode: any, lpFeeReferralShareBps: number) =>
+      program.methods
+        .updateConfig(configParams({ lpMode, lpFeeReferralShareBps }))
+        .accounts({ authority: authority.publicKey, global: globalPda })
+        .rpc();
+
+    // `owner`'s token accounts for both sides of the pool, in cp-swap order
+    const poolSideAccounts = async (mint: PublicKey, owner: PublicKey) => {
+      const pool = getCpmmPool(mint);
+      return Promise.all(
+        [pool.token0Mint, pool.token1Mint].map((side) => createAssociatedTokenAccountIdempotent(connection, authority, side, owner))
+      );
+    };
+
+    // Harvests the LP fees of `mint`, returns what the creator, referrer and protocol received per side
+    const harvestLpFees = async (mint: PublicKey, lpCreator: PublicKey, lpReferrer: PublicKey | null) => {
+      const pool = getCpmmPool(mint);
+      const lpLock = getLpLockPda(mint);
+      const { feeReceiver } = await program.account.global.fetch(globalPda);
+      const creatorTokens = await poolSideAccounts(mint, lpCreator);
+      const protocolTokens = await poolSideAccounts(mint, feeReceiver);
+      const referrerTokens = lpReferrer ? await poolSideAccounts(mint, lpReferrer) : null;
+      const balances = () => Promise.all(
+        [...creatorTokens, ...protocolTokens, ...(referrerTokens ?? [])].map(tokenBalance)
+      );
+
+      const before = await balances();
+      const accounts: any = {
+        caller: cranker.publicKey,
+        global: globalPda,
+        lpLock,
+        cpSwapProgram: CPMM_PROGRAM_ID,
+        authorityRaydium: pool.authority,
+        poolState: pool.poolState,
+        lpMint: pool.lpMint,
+        lpLockTokenAccount: await getAssociatedTokenAddress(pool.lpMint, lpLock, true),
+        token0Vault: pool.token0Vault,
+        token1Vault: pool.token1Vault,
+        vault0Mint: pool.token0Mint,
+        vault1Mint: pool.token1Mint,
+        lockToken0: await getAssociatedTokenAddress(pool.token0Mint, lpLock, true),
+        lockToken1: await getAssociatedTokenAddress(pool.token1Mint, lpLock, true),
+        creatorToken0: creatorTokens[0],
+        creatorToken1: creatorTokens[1],
+        protocolToken0: protocolTokens[0],
+        protocolToken1: protocolTokens[1],
+        referrerToken0: referrerTokens ? referrerTokens[0] : null,
+        referrerToken1: referrerTokens ? referrerTokens[1] : null,
+        memoProgram: MEMO_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tokenProgram2022: TOKEN_2022_PROGRAM_ID,
+        token0Program: TOKEN_PROGRAM_ID,
+        token1Program: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      };
+      await program.methods
+        .harvestLpFees()
+        .accounts(accounts)
+        .preInstructions([ComputeBudgetProgram.setComputeUnitLimit({ units: 400_000 })])
+        .signers([cranker])
+        .rpc();
+      const after = await balances();
+
+      const received = after.map((balance, i) => balance.sub(before[i]));
+      return {
+        creator: received.slice(0, 2),
+        protocol: received.slice(2, 4),
+        referrer: referrerTokens ? received.slice(4, 6) : null,
+      };
+    };
+
+    before(async () => {
+      await airdrop(buyer.publicKey, 100 * LAMPORTS_PER_SOL);
+      await airdrop(cranker.publicKey, 25 * LAMPORTS_PER_SOL);
+      await airdrop(referrer.publicKey, 2 * LAMPORTS_PER_SOL);
+      await airdrop(newCreator.publicKey, 2 * LAMPORTS_PER_SOL);
+      await program.methods
+        .registerReferral()
+        .accounts({ user: referrer.publicKey, referral: getReferralPda(referrer.publicKey), systemProgram: SystemProgram.programId })
+        .signers([referrer])
+        .rpc();
+      // 50% of the LP fees to the creator (default), 10% to the launch referrer, the rest to the protocol
+      await setLpMode({ lock: {} }, 1000);
+    });
+
+    after(async () => {
+      await setLpMode({ burn: {} }, 0);
+    });
+
+    it("should lock the LP and split the harvested pool fees between creator, referrer and protocol", async () => {
+      const { mint, bondingCurvePda } = await createToken(
+        creator, "Locked Pool", "LOCK", "https://example.com/lock.json", null, null, null, getReferralPda(referrer.publicKey)
+      );
+      await buyToken(buyer, mint.publicKey, new anchor.BN(100 * LAMPORTS_PER_SOL), new anchor.BN(0));
+      lockedMint = mint.publicKey;
+
+      const pool = await migrateToRaydium(cranker, lockedMint, TOKEN_PROGRAM_ID, true);
+
+      // the LP sits in the lock instead of being burnt
+      const lpLockPda = getLpLockPda(lockedMint);
+      const lock = await program.account.lpLock.fetch(lpLockPda);
+      expect(lock.creator.toBase58()).to.equal(creator.publicKey.toBase58());
+      expect(lock.referrer!.toBase58()).to.equal(referrer.publicKey.toBase58());
+      expect(lock.poolState.toBase58()).to.equal(pool.poolState.toBase58());
+      expect(lock.lpAmount.gtn(0)).to.be.true;
+      expect((await tokenBalance(await getAssociatedTokenAddress(pool.lpMint, lpLockPda, true))).toString()).to.equal(lock.lpAmount.toString());
+
+      // pool fees grow the liquidity above the locked principal
+      await swapOnPool(lockedMint, bondingCurvePda, null, creator.publicKey, new anchor.BN(10 * LAMPORTS_PER_SOL));
+      const received = await harvestLpFees(lockedMint, creator.publicKey, referrer.publicKey);
+
+      for (const side of [0, 1]) {
+        const total = received.creator[side].add(received.referrer![side]).add(received.protocol[side]);
+        expect(total.gtn(0)).to.be.true;
+        expect(received.creator[side].toString()).to.equal(total.muln(5000).divn(10_000).toString());
+        expect(received.referrer![side].toString()).to.equal(total.muln(1000).divn(10_000).toString());
+      }
+      // only the fee share left the lock
+      const harvested = await program.account.lpLock.fetch(lpLockPda);
+      expect(harvested.lpAmount.lt(lock.lpAmount)).to.be.true;
+      expect(harvested.lpAmount.gtn(0)).to.be.true;
+    });
+
+    it("should send the LP fees to the accepted creator, without referrer accounts once the referral share is off", async () => {
+      const bondingCurvePda = getBondingCurvePda(lockedMint);
+      await program.methods
+        .transferCreator(newCreator.publicKey)
+        .accounts({ creator: creator.publicKey, bondingCurve: bondingCurvePda })
+        .signers([creator])
+        .rpc();
+      await program.methods
+        .acceptCreator()
+        .accounts({ newCreator: newCreator.publicKey, bondingCurve: bondingCurvePda, lpLock: getLpLockPda(lockedMint) })
+        .signers([newCreator])
+        .rpc();
+      expect((await program.account.lpLock.fetch(getLpLockPda(lockedMint))).creator.toBase58()).to.equal(newCreator.publicKey.toBase58());
+
+      await setLpMode({ lock: {} }, 0);
+      await swapOnPool(lockedMint, bondingCurvePda, null, newCreator.publicKey, new anchor.BN(10 * LAMPORTS_PER_SOL));
+      // the curve still has a launch referrer, but its share is 0: no referrer token accounts needed
+      const received = await harvestLpFees(lockedMint, newCreator.publicKey, null);
+
+      for (const side of [0, 1]) {
+        const total = received.creator[side].add(received.protocol[side]);
+        expect(total.gtn(0)).to.be.true;
+        expect(received.creator[side].toString()).to.equal(total.muln(5000).divn(10_000).toString());
+      }
+    });
+  });
+});
@@ -0,0 +1,167 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  program,
+  airdrop,
+  createToken,
+  buyToken,
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  LAMPORTS_PER_SOL,
+  TOKEN_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  getAssociatedTokenAddress,
+} from "./helpers";
+import { getGlobalPda, getBondingCurvePda, getFeeVaultPda, getWalletPurchasePda } from "./helpers/pda";
+import { buildPresaleTree } from "./helpers/merkle";
+
+describe("06 - Presale", () => {
+  const alice = Keypair.generate();
+  const bob = Keypair.generate();
+  const outsider = Keypair.generate();
+  const aliceCap = new anchor.BN("20000000000000"); // 20M tokens
+  const bobCap = new anchor.BN("5000000000000");    // 5M tokens
+
+  let creator: Keypair;
+  let mint: Keypair;
+  let tree: ReturnType<typeof buildPresaleTree>;
+
+  async function presaleBuy(buyer: Keypair, solAmount: anchor.BN, maxTokens: anchor.BN, proof: number[][]) {
+    const bondingCurvePda = getBondingCurvePda(mint.publicKey);
+    const bc = await program.account.bondingCurve.fetch(bondingCurvePda);
+
+    await program.methods
+      .presaleBuy(solAmount, new anchor.BN(0), maxTokens, proof)
+      .accounts({
+        buyer: buyer.publicKey,
+        global: getGlobalPda(),
+        mint: mint.publicKey,
+        bondingCurve: bondingCurvePda,
+        buyerTokenAccount: await getAssociatedTokenAddress(mint.publicKey, buyer.publicKey),
+        tokenAccount: await getAssociatedTokenAddress(mint.publicKey, bondingCurvePda, true),
+        creatorAccount: bc.creator,
+        feeVault: getFeeVaultPda(),
+        referral: null,
+        walletPurchase: getWalletPurchasePda(mint.publicKey, buyer.publicKey),
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      } as any)
+      .signers([buyer])
+      .rpc();
+  }
+
+  before(async () => {
+    creator = Keypair.generate();
+    await airdrop(creator.publicKey, 10 * LAMPORTS_PER_SOL);
+    for (const k of [alice, bob, outsider]) {
+      await airdrop(k.publicKey, 10 * LAMPORTS_PER_SOL);
+    }
+
+    ({ mint } = await createToken(creator, "Presale Token", "PRE", "https://example.com/pre.json"));
+
+    tree = buildPresaleTree([
+      { wallet: alice.publicKey, maxTokens: aliceCap },
+      { wallet: bob.publicKey, maxTokens: bobCap },
+    ]);
+
+    const presaleEnd = new anchor.BN(Math.floor(Date.now() / 1000) + 3600);
+    await program.methods
+      .setPresale(tree.root, presaleEnd)
+      .accounts({
+        creator: creator.publicKey,
+        global: getGlobalPda(),
+        bondingCurve: getBondingCurvePda(mint.publicKey),
+      })
+      .signers([creator])
+      .rpc();
+  });
+
+  it("should store the presale root and window", async () => {
+    const bc = await program.account.bondingCurve.fetch(getBondingCurvePda(mint.publicKey));
+    expect(Array.from(bc.presaleMerkleRoot)).to.deep.equal(tree.root);
+    expect(bc.presaleEndTime.toNumber()).to.be.greaterThan(bc.startTime.toNumber());
+  });
+
+  it("should reject public buys during the presale", async () => {
+    try {
+      await buyToken(outsider, mint.publicKey, new anchor.BN(LAMPORTS_PER_SOL / 10), new anchor.BN(0));
+      expect.fail("Should have thrown");
+    } catch (err: any) {
+      expect(err.toString()).to.satisfy(
+        (s: string) => s.includes("PresaleActive") || s.includes("Presale is active")
+      );
+    }
+  });
+
+  it("should let an allowlisted wallet buy with a valid proof", async () => {
+    await presaleBuy(alice, new anchor.BN(LAMPORTS_PER_SOL / 10), aliceCap, tree.getProof(0));
+
+    const record = await program.account.walletPurchase.fetch(getWalletPurchasePda(mint.publicKey, alice.publicKey));
+    expect(record.tokensBought.toNumber()).to.be.greaterThan(0);
+  });
+
+  it("should reject a buy above the leaf cap", async () => {
+    try {
+      await presaleBuy(bob, new anchor.BN(LAMPORTS_PER_SOL), bobCap, tree.getProof(1));
+      expect.fail("Should have thrown");
+    } catch (err: any) {
+      expect(err.toString()).to.satisfy(
+        (s: string) => s.includes("PresaleCapExceeded") || s.includes("Presale allocation exceeded")
+      );
+    }
+  });
+
+  it("should reject a wallet that is not on the allowlist", async () => {
+    try {
+      await presaleBuy(outsider, new anchor.BN(LAMPORTS_PER_SOL / 10), aliceCap, tree.getProof(0));
+      expect.fail("Should have thrown");
+    } catch (err: any) {
+      expect(err.toString()).to.satisfy(
+        (s: string) => s.includes("InvalidPresaleProof") || s.includes("Invalid presale proof")
+      );
+    }
+  });
+
+  it("should reject set_presale from a non-creator", async () => {
+    try {
+      await program.methods
+        .setPresale(tree.root, new anchor.BN(Math.floor(Date.now() / 1000) + 600))
+        .accounts({
+          creator: outsider.publicKey,
+          global: getGlobalPda(),
+          bondingCurve: getBondingCurvePda(mint.publicKey),
+        })
+        .signers([outsider])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (err: any) {
+      expect(err.toString()).to.satisfy(
+        (s: string) => s.includes("NotCreator") || s.includes("Only the creator")
+      );
+    }
+  });
+
+  it("should reject set_presale once the curve has traded", async () => {
+    const { mint: tradedMint } = await createToken(creator, "Traded Token", "TRD", "https://example.com/trd.json");
+    await buyToken(outsider, tradedMint.publicKey, new anchor.BN(LAMPORTS_PER_SOL / 10), new anchor.BN(0));
+
+    try {
+      await program.methods
+        .setPresale(tree.root, new anchor.BN(Math.floor(Date.now() / 1000) + 600))
+        .accounts({
+          creator: creator.publicKey,
+          global: getGlobalPda(),
+          bondingCurve: getBondingCurvePda(tradedMint.publicKey),
+        })
+        .signers([creator])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (err: any) {
+      expect(err.toString()).to.satisfy(
+        (s: string) => s.includes("PresaleLocked") || s.includes("Presale can only be set before trading opens")
+      );
+    }
+  });
+});
@@ -0,0 +1,183 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  program,
+  connection,
+  airdrop,
+  createToken,
+  createToken2022,
+  buyToken,
+  Keypair,
+  SystemProgram,
+  LAMPORTS_PER_SOL,
+  TOKEN_PROGRAM_ID,
+  TOKEN_2022_PROGRAM_ID,
+  TOKEN_METADATA_PROGRAM_ID,
+} from "./helpers";
+import { getBondingCurvePda } from "./helpers/pda";
+import { getTokenMetadata } from "@solana/spl-token";
+
+describe("07 - Creator transfer", () => {
+  const creator = Keypair.generate();
+  const community = Keypair.generate();
+  const stranger = Keypair.generate();
+  const trader = Keypair.generate();
+
+  let mint: Keypair;
+  let bondingCurvePda: anchor.web3.PublicKey;
+
+  before(async () => {
+    await airdrop(creator.publicKey, 10 * LAMPORTS_PER_SOL);
+    await airdrop(community.publicKey, 2 * LAMPORTS_PER_SOL);
+    await airdrop(stranger.publicKey, 2 * LAMPORTS_PER_SOL);
+    await airdrop(trader.publicKey, 10 * LAMPORTS_PER_SOL);
+
+    ({ mint } = await createToken(creator, "Handover", "HAND", "https://example.com/hand.json"));
+    bondingCurvePda = getBondingCurvePda(mint.publicKey);
+  });
+
+  it("should reject a nomination from someone other than the creator", async () => {
+    try {
+      await program.methods
+        .transferCreator(stranger.publicKey)
+        .accounts({ creator: stranger.publicKey, bondingCurve: bondingCurvePda })
+        .signers([stranger])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (err: any) {
+      expect(err.toString()).to.satisfy(
+        (s: string) => s.includes("NotCreator") || s.includes("Only the creator can do this")
+      );
+    }
+  });
+
+  it("should record the pending creator without handing over rights", async () => {
+    await program.methods
+      .transferCreator(community.publicKey)
+      .accounts({ creator: creator.publicKey, bondingCurve: bondingCurvePda })
+      .signers([creator])
+      .rpc();
+
+    const bc = await program.account.bondingCurve.fetch(bondingCurvePda);
+    expect(bc.creator.toBase58()).to.equal(creator.publicKey.toBase58());
+    expect(bc.pendingCreator!.toBase58()).to.equal(community.publicKey.toBase58());
+  });
+
+  it("should reject acceptance by a wallet that was not nominated", async () => {
+    try {
+      await program.methods
+        .acceptCreator()
+        .accounts({ newCreator: stranger.publicKey, bondingCurve: bondingCurvePda, lpLock: null })
+        .signers([stranger])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (err: any) {
+      expect(err.toString()).to.satisfy(
+        (s: string) => s.includes("NotPendingCreator") || s.includes("Signer is not the pending creator")
+      );
+    }
+  });
+
+  it("should hand over the curve and route creator fees to the new creator", async () => {
+    await program.methods
+      .acceptCreator()
+      .accounts({ newCreator: community.publicKey, bondingCurve: bondingCurvePda, lpLock: null })
+      .signers([community])
+      .rpc();
+
+    const bc = await program.account.bondingCurve.fetch(bondingCurvePda);
+    expect(bc.creator.toBase58()).to.equal(community.publicKey.toBase58());
+    expect(bc.pendingCreator).to.be.null;
+
+    const oldCreatorBefore = await connection.getBalance(creator.publicKey);
+    const newCreatorBefore = await connection.getBalance(community.publicKey);
+
+    await buyToken(trader, mint.publicKey, new anchor.BN(LAMPORTS_PER_SOL), new anchor.BN(0));
+
+    expect(await connection.getBalance(creator.publicKey)).to.equal(oldCreatorBefore);
+    expect(await connection.getBalance(community.publicKey)).to.be.greaterThan(newCreatorBefore);
+  });
+});
+
+describe("07 - Metadata updates", () => {
+  const creator = Keypair.generate();
+
+  before(async () => {
+    await airdrop(creator.publicKey, 10 * LAMPORTS_PER_SOL);
+  });
+
+  it("should update the Metaplex URI of a mutable launch", async () => {
+    const { mint, bondingCurvePda, metadata } = await createToken(
+      creator, "Typo Token", "TYPO", "https://example.com/typo.jsn", null, null, null, null, true
+    );
+
+    const newUri = "https://example.com/typo.json";
+    await program.methods
+      .updateTokenUri(newUri)
+      .accounts({
+        creator: creator.publicKey,
+        bondingCurve: bondingCurvePda,
+        mint: mint.publicKey,
+        metadata,
+        tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([creator])
+      .rpc();
+
+    const metadataInfo = await connection.getAccountInfo(metadata);
+    expect(metadataInfo!.data.toString()).to.contain(newUri);
+  });
+
+  it("should update the Token-2022 URI of a mutable launch", async () => {
+    const { mint, bondingCurvePda } = await createToken2022(
+      creator, "Ext Typo", "EXTY", "ipfs://short", null, null, null, null, true
+    );
+
+    const newUri = "ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi";
+    await program.methods
+      .updateTokenUri(newUri)
+      .accounts({
+        creator: creator.publicKey,
+        bondingCurve: bondingCurvePda,
+        mint: mint.publicKey,
+        metadata: null,
+        tokenMetadataProgram: null,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .signers([creator])
+      .rpc();
+
+    const tokenMetadata = await getTokenMetadata(connection, mint.publicKey, "confirmed", TOKEN_2022_PROGRAM_ID);
+    expect(tokenMetadata!.uri).to.equal(newUri);
+  });
+
+  it("should reject updates on an immutable launch", async () => {
+    const { mint, bondingCurvePda, metadata } = await createToken(
+      creator, "Fixed Token", "FIXD", "https://example.com/fixd.json"
+    );
+
+    try {
+      await program.methods
+        .updateTokenUri("https://example.com/other.json")
+        .accounts({
+          creator: creator.publicKey,
+          bondingCurve: bondingCurvePda,
+          mint: mint.publicKey,
+          metadata,
+          tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([creator])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (err: any) {
+      expect(err.toString()).to.satisfy(
+        (s: string) => s.includes("MetadataImmutable") || s.includes("Token metadata is immutable")
+      );
+    }
+  });
+});
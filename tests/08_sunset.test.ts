@@ -0,0 +1,369 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  program,
+  provider,
+  connection,
+  airdrop,
+  createToken,
+  buyToken,
+  sellToken,
+  migrateToRaydium,
+  Keypair,
+  SystemProgram,
+  LAMPORTS_PER_SOL,
+  TOKEN_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  getAssociatedTokenAddress,
+  DEFAULT_GRADUATION_THRESHOLD,
+  configParams,
+  allowCreatorAllocations,
+} from "./helpers";
+import { getGlobalPda, getBondingCurvePda } from "./helpers/pda";
+
+const DEFAULT_SUNSET_INACTIVITY = 90 * 24 * 60 * 60;
+const DEFAULT_MIGRATION_TIMEOUT = 7 * 24 * 60 * 60;
+
+describe("08 - Sunset", () => {
+  const authority = (provider.wallet as anchor.Wallet).payer;
+  const globalPda = getGlobalPda();
+  const creator = Keypair.generate();
+  const alice = Keypair.generate();
+  const bob = Keypair.generate();
+
+  const setSunsetInactivity = (seconds: number) =>
+    program.methods
+      .updateConfig(configParams({ sunsetInactivity: new anchor.BN(seconds) }))
+      .accounts({ authority: authority.publicKey, global: globalPda })
+      .rpc();
+
+  const sunsetCurve = async (mint: anchor.web3.PublicKey) => {
+    await program.methods
+      .sunsetCurve()
+      .accounts({
+        caller: alice.publicKey,
+        mint,
+        tokenAccount: await getAssociatedTokenAddress(mint, getBondingCurvePda(mint), true),
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([alice])
+      .rpc();
+  };
+
+  const redeem = async (holder: Keypair, mint: anchor.web3.PublicKey, amount: anchor.BN) => {
+    const bondingCurvePda = getBondingCurvePda(mint);
+    await program.methods
+      .redeemSunset(amount)
+      .accounts({
+        holder: holder.publicKey,
+        mint,
+        bondingCurve: bondingCurvePda,
+        tokenAccount: await getAssociatedTokenAddress(mint, bondingCurvePda, true),
+        holderTokenAccount: await getAssociatedTokenAddress(mint, holder.publicKey),
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([holder])
+      .rpc();
+  };
+
+  let mint: Keypair;
+  let bondingCurvePda: anchor.web3.PublicKey;
+
+  before(async () => {
+    await airdrop(creator.publicKey, 10 * LAMPORTS_PER_SOL);
+    await airdrop(alice.publicKey, 10 * LAMPORTS_PER_SOL);
+    await airdrop(bob.publicKey, 10 * LAMPORTS_PER_SOL);
+
+    ({ mint, bondingCurvePda } = await createToken(creator, "Ghost Town", "GHST", "https://example.com/ghst.json"));
+    await buyToken(alice, mint.publicKey, new anchor.BN(LAMPORTS_PER_SOL), new anchor.BN(0));
+    await buyToken(bob, mint.publicKey, new anchor.BN(LAMPORTS_PER_SOL / 2), new anchor.BN(0));
+  });
+
+  after(async () => {
+    await setSunsetInactivity(DEFAULT_SUNSET_INACTIVITY);
+  });
+
+  it("should reject sunset while the curve is still active", async () => {
+    try {
+      await sunsetCurve(mint.publicKey);
+      expect.fail("Should have thrown");
+    } catch (err: any) {
+      expect(err.toString()).to.satisfy(
+        (s: string) => s.includes("CurveStillActive") || s.includes("Curve has traded too recently")
+      );
+    }
+  });
+
+  it("should reject sunset when disabled", async () => {
+    await setSunsetInactivity(0);
+    try {
+      await sunsetCurve(mint.publicKey);
+      expect.fail("Should have thrown");
+    } catch (err: any) {
+      expect(err.toString()).to.satisfy(
+        (s: string) => s.includes("SunsetDisabled") || s.includes("Sunset is disabled")
+      );
+    }
+  });
+
+  it("should sunset an inactive curve and stop trading", async () => {
+    await setSunsetInactivity(1);
+    await new Promise((resolve) => setTimeout(resolve, 2000));
+
+    await sunsetCurve(mint.publicKey);
+
+    const bc = await program.account.bondingCurve.fetch(bondingCurvePda);
+    expect(bc.sunset).to.be.true;
+
+    try {
+      await buyToken(alice, mint.publicKey, new anchor.BN(LAMPORTS_PER_SOL / 10), new anchor.BN(0));
+      expect.fail("Should have thrown");
+    } catch (err: any) {
+      expect(err.toString()).to.satisfy(
+        (s: string) => s.includes("CurveSunset") || s.includes("Curve has been sunset")
+      );
+    }
+
+    try {
+      await sellToken(bob, mint.publicKey, new anchor.BN(1_000_000), new anchor.BN(0));
+      expect.fail("Should have thrown");
+    } catch (err: any) {
+      expect(err.toString()).to.satisfy(
+        (s: string) => s.includes("CurveSunset") || s.includes("Curve has been sunset")
+      );
+    }
+  });
+
+  it("should redeem tokens for a pro-rata share of the reserves", async () => {
+    const aliceAta = await getAssociatedTokenAddress(mint.publicKey, alice.publicKey);
+    const bobAta = await getAssociatedTokenAddress(mint.publicKey, bob.publicKey);
+    const aliceTokens = new anchor.BN((await connection.getTokenAccountBalance(aliceAta)).value.amount);
+    const bobTokens = new anchor.BN((await connection.getTokenAccountBalance(bobAta)).value.amount);
+
+    const bcBefore = await program.account.bondingCurve.fetch(bondingCurvePda);
+    const expected = bcBefore.realSolReserves.mul(aliceTokens).div(aliceTokens.add(bobTokens));
+
+    const aliceBefore = await connection.getBalance(alice.publicKey);
+    await redeem(alice, mint.publicKey, aliceTokens);
+    const aliceAfter = await connection.getBalance(alice.publicKey);
+
+    // alice pays the tx fee
+    expect(aliceAfter - aliceBefore).to.be.at.most(expected.toNumber());
+    expect(aliceAfter - aliceBefore).to.be.greaterThan(expected.toNumber() - 10_000);
+    expect((await connection.getTokenAccountBalance(aliceAta)).value.amount).to.equal("0");
+
+    const bcAfter = await program.account.bondingCurve.fetch(bondingCurvePda);
+    expect(bcAfter.realSolReserves.toString()).to.equal(bcBefore.realSolReserves.sub(expected).toString());
+  });
+
+  it("should refuse to close while tokens are outstanding", async () => {
+    try {
+      await program.methods
+        .closeSunsetCurve()
+        .accounts({
+          caller: alice.publicKey,
+          mint: mint.publicKey,
+          bondingCurve: bondingCurvePda,
+          tokenAccount: await getAssociatedTokenAddress(mint.publicKey, bondingCurvePda, true),
+          vesting: null,
+          vestingTokenAccount: null,
+          creatorAccount: creator.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([alice])
+        .rpc();
+      expect.fail("Should have thrown");
+    } catch (err: any) {
+      expect(err.toString()).to.satisfy(
+        (s: string) => s.includes("RedemptionsOutstanding") || s.includes("Holders still have tokens to redeem")
+      );
+    }
+  });
+
+  it("should close the curve once every holder redeemed", async () => {
+    const bobAta = await getAssociatedTokenAddress(mint.publicKey, bob.publicKey);
+    await redeem(bob, mint.publicKey, new anchor.BN((await connection.getTokenAccountBalance(bobAta)).value.amount));
+
+    const creatorBefore = await connection.getBalance(creator.publicKey);
+    await program.methods
+      .closeSunsetCurve()
+      .accounts({
+        caller: alice.publicKey,
+        mint: mint.publicKey,
+        bondingCurve: bondingCurvePda,
+        tokenAccount: await getAssociatedTokenAddress(mint.publicKey, bondingCurvePda, true),
+        vesting: null,
+        vestingTokenAccount: null,
+        creatorAccount: creator.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([alice])
+      .rpc();
+
+    expect(await connection.getAccountInfo(bondingCurvePda)).to.be.null;
+    expect(await connection.getBalance(creator.publicKey)).to.be.greaterThan(creatorBefore);
+  });
+
+  describe("migration timeout", () => {
+    const setGraduation = (threshold: anchor.BN, timeout: number | null) =>
+      program.methods
+        .updateConfig(configParams({ graduationThreshold: threshold, migrationTimeout: timeout === null ? null : new anchor.BN(timeout) }))
+        .accounts({ authority: authority.publicKey, global: globalPda })
+        .rpc();
+
+    const graduate = async (name: string, symbol: string, creatorAllocationBps: number | null = null) => {
+      const launched = await createToken(
+        creator, name, symbol, `https://example.com/${symbol.toLowerCase()}.json`, null, null, creatorAllocationBps
+      );
+      await buyToken(alice, launched.mint.publicKey, new anchor.BN(3 * LAMPORTS_PER_SOL), new anchor.BN(0));
+      expect((await program.account.bondingCurve.fetch(launched.bondingCurvePda)).completed).to.be.true;
+      return launched;
+    };
+
+    const reopenCurve = (bondingCurve: anchor.web3.PublicKey) =>
+      program.methods
+        .reopenCurve()
+        .accounts({ authority: authority.publicKey, global: globalPda, bondingCurve })
+        .rpc();
+
+    const expectClaimRejected = async (launched: Awaited<ReturnType<typeof graduate>>, error: string, msg: string) => {
+      const { mint: stuckMint, bondingCurvePda: stuckPda, vesting, vestingTokenAccount } = launched;
+      const allocation = (await connection.getTokenAccountBalance(vestingTokenAccount!)).value.amount;
+      try {
+        await program.methods
+          .claimVested()
+          .accounts({
+            creator: creator.publicKey,
+            mint: stuckMint.publicKey,
+            bondingCurve: stuckPda,
+            vesting: vesting!,
+            vestingTokenAccount: vestingTokenAccount!,
+            creatorTokenAccount: await getAssociatedTokenAddress(stuckMint.publicKey, creator.publicKey),
+            systemProgram: SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          })
+          .signers([creator])
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.satisfy((s: string) => s.includes(error) || s.includes(msg));
+      }
+      // the allocation stays locked, the creator has nothing to sell or redeem
+      expect((await connection.getTokenAccountBalance(vestingTokenAccount!)).value.amount).to.equal(allocation);
+      expect(await connection.getAccountInfo(
+        await getAssociatedTokenAddress(stuckMint.publicKey, creator.publicKey)
+      )).to.be.null;
+    };
+
+    before(async () => {
+      await airdrop(alice.publicKey, 10 * LAMPORTS_PER_SOL);
+      await setGraduation(new anchor.BN(2 * LAMPORTS_PER_SOL), null);
+      await allowCreatorAllocations(true);
+    });
+
+    after(async () => {
+      await setGraduation(DEFAULT_GRADUATION_THRESHOLD, DEFAULT_MIGRATION_TIMEOUT);
+      await allowCreatorAllocations(false);
+    });
+
+    it("should keep a completed curve frozen within the timeout", async () => {
+      const { bondingCurvePda: stuckPda } = await graduate("Stuck One", "STK1");
+      try {
+        await reopenCurve(stuckPda);
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.satisfy(
+          (s: string) => s.includes("MigrationPending") || s.includes("Curve is still within its migration timeout")
+        );
+      }
+    });
+
+    it("should let the authority reopen sells once the timeout passed", async () => {
+      await setGraduation(new anchor.BN(2 * LAMPORTS_PER_SOL), 1);
+      const { mint: stuckMint, bondingCurvePda: stuckPda } = await graduate("Stuck Two", "STK2");
+      await new Promise((resolve) => setTimeout(resolve, 2000));
+
+      await reopenCurve(stuckPda);
+      expect((await program.account.bondingCurve.fetch(stuckPda)).sellsReopened).to.be.true;
+
+      try {
+        await buyToken(bob, stuckMint.publicKey, new anchor.BN(LAMPORTS_PER_SOL / 10), new anchor.BN(0));
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.satisfy(
+          (s: string) => s.includes("CurveCompleted") || s.includes("6001")
+        );
+      }
+
+      const aliceAta = await getAssociatedTokenAddress(stuckMint.publicKey, alice.publicKey);
+      const before = new anchor.BN((await connection.getTokenAccountBalance(aliceAta)).value.amount);
+      await sellToken(alice, stuckMint.publicKey, before.divn(2), new anchor.BN(0));
+      const after = new anchor.BN((await connection.getTokenAccountBalance(aliceAta)).value.amount);
+      expect(after.toString()).to.equal(before.sub(before.divn(2)).toString());
+    });
+
+    it("should let anyone sunset a stuck curve for pro-rata redemption", async () => {
+      const { mint: stuckMint, bondingCurvePda: stuckPda } = await graduate("Stuck Three", "STK3");
+      await new Promise((resolve) => setTimeout(resolve, 2000));
+
+      await sunsetCurve(stuckMint.publicKey);
+      const bc = await program.account.bondingCurve.fetch(stuckPda);
+      expect(bc.sunset).to.be.true;
+
+      const aliceAta = await getAssociatedTokenAddress(stuckMint.publicKey, alice.publicKey);
+      const aliceTokens = new anchor.BN((await connection.getTokenAccountBalance(aliceAta)).value.amount);
+      await redeem(alice, stuckMint.publicKey, aliceTokens);
+
+      // alice was the only holder
+      expect((await program.account.bondingCurve.fetch(stuckPda)).realSolReserves.toNumber()).to.equal(0);
+    });
+
+    it("should refuse vesting claims and migration once sells are reopened", async () => {
+      const launched = await graduate("Stuck Vest", "STKV", 500);
+      await expectClaimRejected(launched, "NotMigrated", "Bonding curve has not migrated yet");
+
+      await new Promise((resolve) => setTimeout(resolve, 2000));
+      await reopenCurve(launched.bondingCurvePda);
+      await expectClaimRejected(launched, "NotMigrated", "Bonding curve has not migrated yet");
+
+      // a re-opened curve can't migrate, so it never reaches finalize_migration and a vesting start
+      try {
+        await migrateToRaydium(alice, launched.mint.publicKey);
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.satisfy(
+          (s: string) => s.includes("SellsReopened") || s.includes("Curve re-opened for sells, it can only be sunset")
+        );
+      }
+      expect((await program.account.bondingCurve.fetch(launched.bondingCurvePda)).migrated).to.be.false;
+      await expectClaimRejected(launched, "NotMigrated", "Bonding curve has not migrated yet");
+
+      try {
+        await sellToken(creator, launched.mint.publicKey, new anchor.BN(1), new anchor.BN(0));
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.satisfy(
+          (s: string) => s.includes("AccountNotInitialized") || s.includes("3012")
+        );
+      }
+    });
+
+    it("should refuse vesting claims on a curve sunset after the migration timeout", async () => {
+      const launched = await graduate("Sunset Vest", "SNTV", 500);
+      await new Promise((resolve) => setTimeout(resolve, 2000));
+      await sunsetCurve(launched.mint.publicKey);
+
+      await expectClaimRejected(launched, "NotMigrated", "Bonding curve has not migrated yet");
+
+      try {
+        await redeem(creator, launched.mint.publicKey, new anchor.BN(1));
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.satisfy(
+          (s: string) => s.includes("AccountNotInitialized") || s.includes("3012")
+        );
+      }
+    });
+  });
+});
@@ -0,0 +1,51 @@
+import { createHash } from "crypto";
+import { PublicKey } from "@solana/web3.js";
+import * as anchor from "@coral-xyz/anchor";
+
+function sha256(...parts: Buffer[]): Buffer {
+  const h = createHash("sha256");
+  parts.forEach((p) => h.update(p));
+  return h.digest();
+}
+
+function hashPair(a: Buffer, b: Buffer): Buffer {
+  return Buffer.compare(a, b) <= 0 ? sha256(a, b) : sha256(b, a);
+}
+
+/**
+ * Presale leaf, matching utils/merkle.rs: sha256(wallet || max_tokens LE).
+ */
+export function presaleLeaf(wallet: PublicKey, maxTokens: anchor.BN): Buffer {
+  return sha256(wallet.toBuffer(), maxTokens.toArrayLike(Buffer, "le", 8));
+}
+
+/**
+ * Build a sorted-pair merkle tree over the allowlist.
+ * Returns the root and a proof getter per leaf index.
+ */
+export function buildPresaleTree(entries: { wallet: PublicKey; maxTokens: anchor.BN }[]) {
+  const layers: Buffer[][] = [entries.map((e) => presaleLeaf(e.wallet, e.maxTokens))];
+  while (layers[layers.length - 1].length > 1) {
+    const prev = layers[layers.length - 1];
+    const next: Buffer[] = [];
+    for (let i = 0; i < prev.length; i += 2) {
+      next.push(i + 1 < prev.length ? hashPair(prev[i], prev[i + 1]) : prev[i]);
+    }
+    layers.push(next);
+  }
+
+  const root = layers[layers.length - 1][0];
+  const getProof = (index: number): number[][] => {
+    const proof: number[][] = [];
+    for (let level = 0; level < layers.length - 1; level++) {
+      const sibling = index ^ 1;
+      if (sibling < layers[level].length) {
+        proof.push(Array.from(layers[level][sibling]));
+      }
+      index = Math.floor(index / 2);
+    }
+    return proof;
+  };
+
+  return { root: Array.from(root), getProof };
+}
@@ -1,10 +1,18 @@
 import { PublicKey } from "@solana/web3.js";
-import { program } from "./setup";
+import { NATIVE_MINT } from "@solana/spl-token";
+import { program, CPMM_PROGRAM_ID, CPMM_AMM_CONFIG } from "./setup";
 
 const GLOBAL_SEED = Buffer.from("global");
 const BONDING_CURVE_SEED = Buffer.from("bonding-curve");
 const FEE_VAULT_SEED = Buffer.from("fee-vault");
 const REFERRAL_SEED = Buffer.from("referral");
+const WALLET_PURCHASE_SEED = Buffer.from("wallet-purchase");
+const VESTING_SEED = Buffer.from("vesting");
+const LP_LOCK_SEED = Buffer.from("lp-lock");
+const GRADUATION_SEED = Buffer.from("graduation");
+const MIGRATION_TOKEN_SEED = Buffer.from("migration-token");
+const MIGRATION_WSOL_SEED = Buffer.from("migration-wsol");
+const SWAP_WSOL_SEED = Buffer.from("swap-wsol");
 
 export function getGlobalPda(): PublicKey {
   const [pda] = PublicKey.findProgramAddressSync(
@@ -37,3 +45,87 @@ export function getReferralPda(user: PublicKey): PublicKey {
   );
   return pda;
 }
+
+export function getWalletPurchasePda(mint: PublicKey, wallet: PublicKey): PublicKey {
+  const [pda] = PublicKey.findProgramAddressSync(
+    [WALLET_PURCHASE_SEED, mint.toBuffer(), wallet.toBuffer()],
+    program.programId
+  );
+  return pda;
+}
+
+export function getVestingPda(mint: PublicKey): PublicKey {
+  const [pda] = PublicKey.findProgramAddressSync(
+    [VESTING_SEED, mint.toBuffer()],
+    program.programId
+  );
+  return pda;
+}
+
+export function getLpLockPda(mint: PublicKey): PublicKey {
+  const [pda] = PublicKey.findProgramAddressSync(
+    [LP_LOCK_SEED, mint.toBuffer()],
+    program.programId
+  );
+  return pda;
+}
+
+export function getGraduationRecordPda(mint: PublicKey): PublicKey {
+  const [pda] = PublicKey.findProgramAddressSync(
+    [GRADUATION_SEED, mint.toBuffer()],
+    program.programId
+  );
+  return pda;
+}
+
+export function getMigrationTokenPda(mint: PublicKey): PublicKey {
+  const [pda] = PublicKey.findProgramAddressSync(
+    [MIGRATION_TOKEN_SEED, mint.toBuffer()],
+    program.programId
+  );
+  return pda;
+}
+
+export function getMigrationWsolPda(mint: PublicKey): PublicKey {
+  const [pda] = PublicKey.findProgramAddressSync(
+    [MIGRATION_WSOL_SEED, mint.toBuffer()],
+    program.programId
+  );
+  return pda;
+}
+
+export function getSwapWsolPda(trader: PublicKey): PublicKey {
+  const [pda] = PublicKey.findProgramAddressSync(
+    [SWAP_WSOL_SEED, trader.toBuffer()],
+    program.programId
+  );
+  return pda;
+}
+
+/**
+ * Raydium CPMM accounts of the mint/wSOL pool on AmmConfig index 0.
+ * cp-swap orders the pair by mint key.
+ */
+export function getCpmmPool(mint: PublicKey) {
+  const mintIsToken0 = Buffer.compare(mint.toBuffer(), NATIVE_MINT.toBuffer()) < 0;
+  const token0Mint = mintIsToken0 ? mint : NATIVE_MINT;
+  const token1Mint = mintIsToken0 ? NATIVE_MINT : mint;
+  const find = (seeds: Buffer[]) => PublicKey.findProgramAddressSync(seeds, CPMM_PROGRAM_ID)[0];
+
+  const poolState = find([Buffer.from("pool"), CPMM_AMM_CONFIG.toBuffer(), token0Mint.toBuffer(), token1Mint.toBuffer()]);
+  const token0Vault = find([Buffer.from("pool_vault"), poolState.toBuffer(), token0Mint.toBuffer()]);
+  const token1Vault = find([Buffer.from("pool_vault"), poolState.toBuffer(), token1Mint.toBuffer()]);
+  return {
+    mintIsToken0,
+    token0Mint,
+    token1Mint,
+    poolState,
+    token0Vault,
+    token1Vault,
+    tokenVault: mintIsToken0 ? token0Vault : token1Vault,
+    wsolVault: mintIsToken0 ? token1Vault : token0Vault,
+    authority: find([Buffer.from("vault_and_lp_mint_auth_seed")]),
+    lpMint: find([Buffer.from("pool_lp_mint"), poolState.toBuffer()]),
+    observationState: find([Buffer.from("observation"), poolState.toBuffer()]),
+  };
+}
@@ -6,16 +6,19 @@ import {
   PublicKey,
   LAMPORTS_PER_SOL,
   SystemProgram,
+  ComputeBudgetProgram,
 } from "@solana/web3.js";
 import {
   TOKEN_PROGRAM_ID,
+  TOKEN_2022_PROGRAM_ID,
   ASSOCIATED_TOKEN_PROGRAM_ID,
+  NATIVE_MINT,
   getAssociatedTokenAddress,
 } from "@solana/spl-token";
 
 // Re-export for convenience
 export { Keypair, PublicKey, LAMPORTS_PER_SOL, SystemProgram };
-export { TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID, getAssociatedTokenAddress };
+export { TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID, getAssociatedTokenAddress };
 
 // Program setup
 export const provider = anchor.AnchorProvider.env();
@@ -28,6 +31,21 @@ export const TOKEN_METADATA_PROGRAM_ID = new PublicKey(
   "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s"
 );
 
+// Raydium CPMM, cloned from mainnet (see Anchor.toml)
+export const CPMM_PROGRAM_ID = new PublicKey(
+  "CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C"
+);
+export const CPMM_AMM_CONFIG = new PublicKey(
+  "D4FPEruKEHrG5TenZ2mpDGEfu1iUvTiqBxvpU8HLBvC2"
+);
+export const CPMM_CREATE_POOL_FEE = new PublicKey(
+  "DNXgeM9EiiaAbaWvwjHj9fQQLAX5ZsfHyvmYUNRAdNC8"
+);
+// SPL Memo, required by cp-swap withdraw (harvest_lp_fees)
+export const MEMO_PROGRAM_ID = new PublicKey(
+  "MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr"
+);
+
 // Constants matching the Rust program
 export const DEFAULT_VIRTUAL_SOL = new anchor.BN(30 * LAMPORTS_PER_SOL);
 export const DEFAULT_VIRTUAL_TOKENS = new anchor.BN("1073000000000000"); // 1_073_000_000 * 10^6
@@ -36,7 +54,47 @@ export const DEFAULT_TOKEN_SUPPLY = new anchor.BN("1000000000000000");  // 1_000
 export const DEFAULT_TRADE_FEE_BPS = 100;
 export const DEFAULT_CREATOR_SHARE_BPS = 6500;
 export const DEFAULT_REFERRAL_SHARE_BPS = 1000;
+export const DEFAULT_MIN_REFERRAL_SHARE_BPS = 500;
+export const DEFAULT_MAX_REFERRAL_SHARE_BPS = 5000;
+export const DEFAULT_CREATION_FEE = new anchor.BN(LAMPORTS_PER_SOL / 50);
 export const DEFAULT_GRADUATION_THRESHOLD = new anchor.BN(85 * LAMPORTS_PER_SOL);
+export const DEFAULT_MIGRATION_FEE = new anchor.BN(LAMPORTS_PER_SOL / 2);
+export const DEFAULT_MIGRATION_CRANK_REWARD = new anchor.BN(LAMPORTS_PER_SOL / 20);
+export const DEFAULT_MAX_START_DELAY = 7 * 24 * 60 * 60;
+export const DEFAULT_MAX_CREATOR_ALLOCATION_BPS = 0;
+
+// 100M fewer tokens on sale: a sold-out curve leaves ~6% of the supply spare in the vault at migration
+export const ALLOCATION_REAL_TOKENS = new anchor.BN("693100000000000"); // 693_100_000 * 10^6
+export const ALLOCATION_MAX_BPS = 500;
+
+export type UpdateConfigParams = anchor.IdlTypes<TokenLp>["updateConfigParams"];
+
+/**
+ * update_config params: only the given settings change, every other field is sent as None.
+ * Field names come from the IDL so new settings don't touch existing callers.
+ */
+export function configParams(params: Partial<UpdateConfigParams>): UpdateConfigParams {
+  const def = program.idl.types!.find((t) => t.name === "updateConfigParams")!;
+  const fields = (def.type as { fields: { name: string }[] }).fields;
+  const full: any = {};
+  for (const field of fields) {
+    full[field.name] = (params as any)[field.name] ?? null;
+  }
+  return full as UpdateConfigParams;
+}
+
+/**
+ * Switch Global to the curve with room for creator allocations (up to ALLOCATION_MAX_BPS), or back to the defaults.
+ */
+export async function allowCreatorAllocations(allow: boolean): Promise<void> {
+  const { getGlobalPda } = await import("./pda");
+  await program.methods
+    .updateConfig(configParams(allow
+      ? { initialRealTokenReserves: ALLOCATION_REAL_TOKENS, maxCreatorAllocationBps: ALLOCATION_MAX_BPS }
+      : { initialRealTokenReserves: DEFAULT_REAL_TOKENS, maxCreatorAllocationBps: DEFAULT_MAX_CREATOR_ALLOCATION_BPS }))
+    .accounts({ authority: provider.wallet.publicKey, global: getGlobalPda() })
+    .rpc();
+}
 
 /**
  * Airdrop SOL to a public key and confirm.
@@ -70,10 +128,15 @@ export async function createToken(
   creator: Keypair,
   name: string = "Test Token",
   symbol: string = "TEST",
-  uri: string = "https://example.com/metadata.json"
+  uri: string = "https://example.com/metadata.json",
+  referralShareBps: number | null = null,
+  startTime: anchor.BN | null = null,
+  creatorAllocationBps: number | null = null,
+  referral: PublicKey | null = null,
+  mutableMetadata: boolean | null = null
 ) {
   const mint = Keypair.generate();
-  const { getGlobalPda, getBondingCurvePda, getFeeVaultPda } = await import("./pda");
+  const { getGlobalPda, getBondingCurvePda, getFeeVaultPda, getVestingPda } = await import("./pda");
 
   const globalPda = getGlobalPda();
   const bondingCurvePda = getBondingCurvePda(mint.publicKey);
@@ -85,14 +148,24 @@ export async function createToken(
   );
   const metadata = getMetadataPda(mint.publicKey);
 
+  // Vesting accounts are only passed with a creator allocation
+  const vesting = creatorAllocationBps ? getVestingPda(mint.publicKey) : null;
+  const vestingTokenAccount = vesting
+    ? await getAssociatedTokenAddress(mint.publicKey, vesting, true)
+    : null;
+
   await program.methods
-    .createToken(name, symbol, uri)
+    .createToken(name, symbol, uri, referralShareBps, startTime, creatorAllocationBps, mutableMetadata)
     .accounts({
       creator: creator.publicKey,
       global: globalPda,
       mint: mint.publicKey,
       bondingCurve: bondingCurvePda,
       tokenAccount: tokenAccount,
+      feeVault: feeVaultPda,
+      referral: referral,
+      vesting: vesting,
+      vestingTokenAccount: vestingTokenAccount,
       metadata: metadata,
       systemProgram: SystemProgram.programId,
       tokenProgram: TOKEN_PROGRAM_ID,
@@ -103,7 +176,156 @@ export async function createToken(
     .signers([creator, mint])
     .rpc();
 
-  return { mint, bondingCurvePda, tokenAccount, metadata };
+  return { mint, bondingCurvePda, tokenAccount, metadata, vesting, vestingTokenAccount };
+}
+
+/**
+ * Helper to create a Token-2022 token (metadata stored in the mint) via create_token_2022.
+ */
+export async function createToken2022(
+  creator: Keypair,
+  name: string = "Test Token",
+  symbol: string = "TEST",
+  uri: string = "https://example.com/metadata.json",
+  referralShareBps: number | null = null,
+  startTime: anchor.BN | null = null,
+  creatorAllocationBps: number | null = null,
+  referral: PublicKey | null = null,
+  mutableMetadata: boolean | null = null
+) {
+  const mint = Keypair.generate();
+  const { getGlobalPda, getBondingCurvePda, getFeeVaultPda, getVestingPda } = await import("./pda");
+
+  const globalPda = getGlobalPda();
+  const bondingCurvePda = getBondingCurvePda(mint.publicKey);
+  const feeVaultPda = getFeeVaultPda();
+  const tokenAccount = await getAssociatedTokenAddress(
+    mint.publicKey,
+    bondingCurvePda,
+    true,
+    TOKEN_2022_PROGRAM_ID
+  );
+
+  const vesting = creatorAllocationBps ? getVestingPda(mint.publicKey) : null;
+  const vestingTokenAccount = vesting
+    ? await getAssociatedTokenAddress(mint.publicKey, vesting, true, TOKEN_2022_PROGRAM_ID)
+    : null;
+
+  await program.methods
+    .createToken2022(name, symbol, uri, referralShareBps, startTime, creatorAllocationBps, mutableMetadata)
+    .accounts({
+      creator: creator.publicKey,
+      global: globalPda,
+      mint: mint.publicKey,
+      bondingCurve: bondingCurvePda,
+      tokenAccount: tokenAccount,
+      feeVault: feeVaultPda,
+      referral: referral,
+      vesting: vesting,
+      vestingTokenAccount: vestingTokenAccount,
+      systemProgram: SystemProgram.programId,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+    })
+    .signers([creator, mint])
+    .rpc();
+
+  return { mint, bondingCurvePda, tokenAccount, vesting, vestingTokenAccount };
+}
+
+/**
+ * Helper to launch a token with the creator's bundled first buy (create_and_buy_token).
+ * `trackPurchase` passes the creator's WalletPurchase PDA, required during an opening window.
+ */
+export async function createAndBuyToken(
+  creator: Keypair,
+  solAmount: anchor.BN,
+  name: string = "Test Token",
+  symbol: string = "TEST",
+  uri: string = "https://example.com/metadata.json",
+  trackPurchase: boolean = false,
+  minTokensOut: anchor.BN = new anchor.BN(0),
+  referral: PublicKey | null = null
+) {
+  const mint = Keypair.generate();
+  const { getGlobalPda, getBondingCurvePda, getFeeVaultPda, getWalletPurchasePda } = await import("./pda");
+
+  const bondingCurvePda = getBondingCurvePda(mint.publicKey);
+  const tokenAccount = await getAssociatedTokenAddress(mint.publicKey, bondingCurvePda, true);
+
+  await program.methods
+    .createAndBuyToken(name, symbol, uri, solAmount, minTokensOut, null, null, null, null)
+    .accounts({
+      creator: creator.publicKey,
+      global: getGlobalPda(),
+      mint: mint.publicKey,
+      bondingCurve: bondingCurvePda,
+      creatorTokenAccount: await getAssociatedTokenAddress(mint.publicKey, creator.publicKey),
+      feeVault: getFeeVaultPda(),
+      tokenAccount,
+      referral,
+      walletPurchase: trackPurchase ? getWalletPurchasePda(mint.publicKey, creator.publicKey) : null,
+      vesting: null,
+      vestingTokenAccount: null,
+      metadata: getMetadataPda(mint.publicKey),
+      systemProgram: SystemProgram.programId,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+      rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+    })
+    .signers([creator, mint])
+    .rpc();
+
+  return { mint, bondingCurvePda, tokenAccount };
+}
+
+/**
+ * Helper to launch a Token-2022 token with the creator's bundled first buy (create_and_buy_token_2022).
+ */
+export async function createAndBuyToken2022(
+  creator: Keypair,
+  solAmount: anchor.BN,
+  name: string = "Test Token",
+  symbol: string = "TEST",
+  uri: string = "https://example.com/metadata.json",
+  minTokensOut: anchor.BN = new anchor.BN(0)
+) {
+  const mint = Keypair.generate();
+  const { getGlobalPda, getBondingCurvePda, getFeeVaultPda } = await import("./pda");
+
+  const bondingCurvePda = getBondingCurvePda(mint.publicKey);
+  const tokenAccount = await getAssociatedTokenAddress(mint.publicKey, bondingCurvePda, true, TOKEN_2022_PROGRAM_ID);
+
+  const accounts: any = {
+    create: {
+      creator: creator.publicKey,
+      global: getGlobalPda(),
+      mint: mint.publicKey,
+      bondingCurve: bondingCurvePda,
+      tokenAccount,
+      feeVault: getFeeVaultPda(),
+      referral: null,
+      vesting: null,
+      vestingTokenAccount: null,
+      systemProgram: SystemProgram.programId,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+    },
+    creatorTokenAccount: await getAssociatedTokenAddress(mint.publicKey, creator.publicKey, false, TOKEN_2022_PROGRAM_ID),
+    walletPurchase: null,
+    systemProgram: SystemProgram.programId,
+    tokenProgram: TOKEN_2022_PROGRAM_ID,
+    associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+  };
+
+  await program.methods
+    .createAndBuyToken2022(name, symbol, uri, solAmount, minTokensOut, null, null, null, null)
+    .accounts(accounts)
+    .signers([creator, mint])
+    .rpc();
+
+  return { mint, bondingCurvePda, tokenAccount };
 }
 
 /**
@@ -114,15 +336,17 @@ export async function buyToken(
   mint: PublicKey,
   solAmount: anchor.BN,
   minTokensOut: anchor.BN,
-  referral?: PublicKey
+  referral?: PublicKey,
+  walletPurchase?: PublicKey,
+  tokenProgram: PublicKey = TOKEN_PROGRAM_ID
 ) {
   const { getGlobalPda, getBondingCurvePda, getFeeVaultPda } = await import("./pda");
 
   const globalPda = getGlobalPda();
   const bondingCurvePda = getBondingCurvePda(mint);
   const feeVaultPda = getFeeVaultPda();
-  const tokenAccount = await getAssociatedTokenAddress(mint, bondingCurvePda, true);
-  const buyerTokenAccount = await getAssociatedTokenAddress(mint, buyer.publicKey);
+  const tokenAccount = await getAssociatedTokenAddress(mint, bondingCurvePda, true, tokenProgram);
+  const buyerTokenAccount = await getAssociatedTokenAddress(mint, buyer.publicKey, false, tokenProgram);
 
   // Fetch the bonding curve to get the creator
   const bc = await program.account.bondingCurve.fetch(bondingCurvePda);
@@ -137,8 +361,9 @@ export async function buyToken(
     creatorAccount: bc.creator,
     feeVault: feeVaultPda,
     referral: referral || null,
+    walletPurchase: walletPurchase || null,
     systemProgram: SystemProgram.programId,
-    tokenProgram: TOKEN_PROGRAM_ID,
+    tokenProgram,
     associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
   };
 
@@ -157,15 +382,16 @@ export async function sellToken(
   mint: PublicKey,
   tokenAmount: anchor.BN,
   minSolOut: anchor.BN,
-  referral?: PublicKey
+  referral?: PublicKey,
+  tokenProgram: PublicKey = TOKEN_PROGRAM_ID
 ) {
   const { getGlobalPda, getBondingCurvePda, getFeeVaultPda } = await import("./pda");
 
   const globalPda = getGlobalPda();
   const bondingCurvePda = getBondingCurvePda(mint);
   const feeVaultPda = getFeeVaultPda();
-  const tokenAccount = await getAssociatedTokenAddress(mint, bondingCurvePda, true);
-  const sellerTokenAccount = await getAssociatedTokenAddress(mint, seller.publicKey);
+  const tokenAccount = await getAssociatedTokenAddress(mint, bondingCurvePda, true, tokenProgram);
+  const sellerTokenAccount = await getAssociatedTokenAddress(mint, seller.publicKey, false, tokenProgram);
 
   // Fetch the bonding curve to get the creator
   const bc = await program.account.bondingCurve.fetch(bondingCurvePda);
@@ -181,7 +407,7 @@ export async function sellToken(
     feeVault: feeVaultPda,
     referral: referral || null,
     systemProgram: SystemProgram.programId,
-    tokenProgram: TOKEN_PROGRAM_ID,
+    tokenProgram,
     associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
   };
 
@@ -191,3 +417,109 @@ export async function sellToken(
     .signers([seller])
     .rpc();
 }
+
+/**
+ * Accounts of migrate_to_raydium (leftover tokens burnt, LP burnt unless `lockLp`), with the pool they create.
+ */
+async function migrationAccounts(caller: Keypair, mint: PublicKey, tokenProgram: PublicKey, lockLp: boolean = false) {
+  const { getGlobalPda, getBondingCurvePda, getFeeVaultPda, getMigrationTokenPda, getMigrationWsolPda, getCpmmPool, getLpLockPda } = await import("./pda");
+
+  const bondingCurvePda = getBondingCurvePda(mint);
+  const pool = getCpmmPool(mint);
+
+  const accounts: any = {
+    caller: caller.publicKey,
+    global: getGlobalPda(),
+    mint,
+    bondingCurve: bondingCurvePda,
+    tokenAccount: await getAssociatedTokenAddress(mint, bondingCurvePda, true, tokenProgram),
+    poolTokenAccount: getMigrationTokenPda(mint),
+    leftoverTokenAccount: null,
+    feeVault: getFeeVaultPda(),
+    cpSwapProgram: CPMM_PROGRAM_ID,
+    ammConfig: CPMM_AMM_CONFIG,
+    authorityRaydium: pool.authority,
+    poolState: pool.poolState,
+    token0Mint: pool.token0Mint,
+    token1Mint: pool.token1Mint,
+    lpMint: pool.lpMint,
+    wsolMint: NATIVE_MINT,
+    wsolAccount: getMigrationWsolPda(mint),
+    creatorLpToken: await getAssociatedTokenAddress(pool.lpMint, caller.publicKey),
+    token0Vault: pool.token0Vault,
+    token1Vault: pool.token1Vault,
+    createPoolFee: CPMM_CREATE_POOL_FEE,
+    observationState: pool.observationState,
+    metadata: null,
+    tokenMetadataProgram: null,
+    lpLock: lockLp ? getLpLockPda(mint) : null,
+    lpLockTokenAccount: lockLp ? await getAssociatedTokenAddress(pool.lpMint, getLpLockPda(mint), true) : null,
+    tokenProgram: TOKEN_PROGRAM_ID,
+    mintTokenProgram: tokenProgram,
+    token0Program: pool.mintIsToken0 ? tokenProgram : TOKEN_PROGRAM_ID,
+    token1Program: pool.mintIsToken0 ? TOKEN_PROGRAM_ID : tokenProgram,
+    associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+    systemProgram: SystemProgram.programId,
+    rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+  };
+
+  return { accounts, pool };
+}
+
+/**
+ * Helper to migrate a completed curve to Raydium CPMM, with `lockLp` when Global.lp_mode is Lock.
+ * Returns the pool accounts.
+ */
+export async function migrateToRaydium(
+  caller: Keypair,
+  mint: PublicKey,
+  tokenProgram: PublicKey = TOKEN_PROGRAM_ID,
+  lockLp: boolean = false
+) {
+  const { accounts, pool } = await migrationAccounts(caller, mint, tokenProgram, lockLp);
+
+  await program.methods
+    .migrateToRaydium()
+    .accounts(accounts)
+    .preInstructions([ComputeBudgetProgram.setComputeUnitLimit({ units: 600_000 })])
+    .signers([caller])
+    .rpc();
+
+  return pool;
+}
+
+/**
+ * Helper to buy the rest of a curve and migrate it in the same transaction.
+ * Returns the pool accounts.
+ */
+export async function buyAndMigrate(
+  buyer: Keypair,
+  mint: PublicKey,
+  solAmount: anchor.BN,
+  minTokensOut: anchor.BN,
+  tokenProgram: PublicKey = TOKEN_PROGRAM_ID
+) {
+  const { getBondingCurvePda } = await import("./pda");
+  const { accounts: migrate, pool } = await migrationAccounts(buyer, mint, tokenProgram);
+  const bc = await program.account.bondingCurve.fetch(getBondingCurvePda(mint));
+
+  const accounts: any = {
+    migrate,
+    buyerTokenAccount: await getAssociatedTokenAddress(mint, buyer.publicKey, false, tokenProgram),
+    creatorAccount: bc.creator,
+    referral: null,
+    walletPurchase: null,
+    systemProgram: SystemProgram.programId,
+    tokenProgram,
+    associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+  };
+
+  await program.methods
+    .buyAndMigrate(solAmount, minTokensOut)
+    .accounts(accounts)
+    .preInstructions([ComputeBudgetProgram.setComputeUnitLimit({ units: 800_000 })])
+    .signers([buyer])
+    .rpc();
+
+  return pool;
+}